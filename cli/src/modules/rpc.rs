@@ -290,6 +290,43 @@ impl Rpc {
 
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetTransaction => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id argument"));
+                }
+                let transaction_id = argv.remove(0);
+                let transaction_id = RpcHash::from_hex(transaction_id.as_str())?;
+                let result = rpc.get_transaction_call(None, GetTransactionRequest { transaction_id }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetTransactionsByIds => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id argument(s)"));
+                }
+                let transaction_ids =
+                    argv.iter().map(|id| RpcHash::from_hex(id.as_str())).collect::<std::result::Result<Vec<_>, _>>()?;
+                let result = rpc.get_transactions_by_ids_call(None, GetTransactionsByIdsRequest { transaction_ids }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetAddressHistory => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing address argument"));
+                }
+                let address = Address::try_from(argv.remove(0).as_str())?;
+                let limit = argv.first().and_then(|x| x.parse::<u32>().ok()).unwrap_or_default();
+                let cursor = argv.get(1).cloned();
+                let result = rpc
+                    .get_address_history_call(
+                        None,
+                        GetAddressHistoryRequest { address, start_daa_score: None, end_daa_score: None, cursor, limit },
+                    )
+                    .await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::CreateBackup => {
+                let result = rpc.create_backup_call(None, CreateBackupRequest {}).await?;
+                self.println(&ctx, result);
+            }
             _ => {
                 tprintln!(ctx, "rpc method exists but is not supported by the cli: '{op_str}'\r\n");
                 return Ok(());
@@ -1,8 +1,18 @@
 use itertools::Itertools;
-use karlsen_consensus_core::api::{ConsensusApi, DynConsensus};
+use karlsen_consensus_core::{
+    api::{ConsensusApi, DynConsensus},
+    errors::consensus::ConsensusResult,
+    Hash,
+};
 use karlsen_core::{core::Core, debug, service::Service};
 use parking_lot::RwLock;
-use std::{collections::VecDeque, ops::Deref, sync::Arc, thread::JoinHandle};
+use std::{
+    collections::VecDeque,
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread::JoinHandle,
+};
 
 mod batch;
 mod session;
@@ -24,6 +34,10 @@ pub trait ConsensusCtl: Sync + Send {
 
     /// Set as current active consensus
     fn make_active(&self);
+
+    /// Create a consistent checkpoint of the consensus DB under `consensus_db_root` (keeping the directory name
+    /// of the live DB) and of the multi-consensus management DB at `meta_db_dir`. Returns the consensus checkpoint path
+    fn create_checkpoint(&self, consensus_db_root: &Path, meta_db_dir: &Path) -> ConsensusResult<PathBuf>;
 }
 
 pub type DynConsensusCtl = Arc<dyn ConsensusCtl>;
@@ -111,6 +125,16 @@ impl ManagerInner {
     }
 }
 
+/// A checkpoint of the active consensus DB along with the consensus state it captures
+#[derive(Clone, Debug)]
+pub struct ConsensusCheckpoint {
+    /// Path of the consensus DB checkpoint
+    pub path: PathBuf,
+    /// The sink when the checkpoint was taken. Blocks added concurrently might be included as well
+    pub sink: Hash,
+    pub pruning_point: Hash,
+}
+
 pub struct ConsensusManager {
     factory: Arc<dyn ConsensusFactory>,
     inner: RwLock<ManagerInner>,
@@ -171,6 +195,18 @@ impl ConsensusManager {
     pub fn delete_staging_entry(&self) {
         self.factory.delete_staging_entry();
     }
+
+    /// Checkpoints the current consensus DB along with the management DB (see [`ConsensusCtl::create_checkpoint`]).
+    /// The current consensus cannot be replaced by a staging consensus nor be pruned while the checkpoint is being taken
+    pub fn create_checkpoint(&self, consensus_db_root: &Path, meta_db_dir: &Path) -> ConsensusResult<ConsensusCheckpoint> {
+        let g = self.inner.read();
+        let session = futures::executor::block_on(g.current.consensus.session_blocking());
+        // Read before checkpointing so that the recorded sink is guaranteed to be included
+        let sink = session.get_sink();
+        let pruning_point = session.pruning_point();
+        let path = g.current.ctl.create_checkpoint(consensus_db_root, meta_db_dir)?;
+        Ok(ConsensusCheckpoint { path, sink, pruning_point })
+    }
 }
 
 impl Service for ConsensusManager {
@@ -17,6 +17,15 @@ pub enum ConfigError {
     #[error("Configuration: --max-tracked-addresses cannot be set above {0}")]
     MaxTrackedAddressesTooHigh(usize),
 
+    #[error("Configuration: --stratum-listen requires --stratum-pay-address")]
+    MissingStratumPayAddress,
+
+    #[error("Configuration: --stratum-pay-address {0} is not a valid address of the selected network")]
+    InvalidStratumPayAddress(String),
+
+    #[error("Configuration: {0}")]
+    InvalidRpcAccessPolicy(String),
+
     #[cfg(feature = "devnet-prealloc")]
     #[error("Cannot preallocate UTXOs on any network except devnet")]
     PreallocUtxosOnNonDevnet,
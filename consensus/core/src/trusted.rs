@@ -31,6 +31,7 @@ impl TrustedBlock {
 /// Represents an externally provided header with associated Ghostdag data which
 /// is only partially validated by the consensus layer. Note there is no actual trust
 /// but rather these headers are indirectly validated through the PoW mined over them
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TrustedHeader {
     pub header: Arc<Header>,
     pub ghostdag: ExternalGhostdagData,
@@ -43,6 +44,7 @@ impl TrustedHeader {
 }
 
 /// Represents externally provided Ghostdag data associated with a block Hash
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TrustedGhostdagData {
     pub hash: Hash,
     pub ghostdag: ExternalGhostdagData,
@@ -1,9 +1,10 @@
 use super::{factory::MultiConsensusManagementStore, Consensus};
+use karlsen_consensus_core::errors::consensus::{ConsensusError, ConsensusResult};
 use karlsen_consensusmanager::ConsensusCtl;
 use karlsen_database::prelude::DB;
 use parking_lot::RwLock;
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, Weak},
     thread::JoinHandle,
 };
@@ -40,6 +41,21 @@ impl ConsensusCtl for Ctl {
         // TODO: pass a value to make sure the correct consensus is committed
         self.management_store.write().commit_staging_consensus().unwrap();
     }
+
+    fn create_checkpoint(&self, consensus_db_root: &Path, meta_db_dir: &Path) -> ConsensusResult<PathBuf> {
+        let consensus_db = self.consensus_db_ref.upgrade().ok_or(ConsensusError::General("consensus DB was already closed"))?;
+        let dir_name = self.consensus_db_path.file_name().ok_or(ConsensusError::General("invalid consensus DB path"))?;
+        let checkpoint_path = consensus_db_root.join(dir_name);
+        // Hold the management store lock so that consensus entries cannot be created or committed meanwhile
+        let management_store = self.management_store.read();
+        consensus_db
+            .create_checkpoint(&checkpoint_path)
+            .map_err(|err| ConsensusError::GeneralOwned(format!("consensus DB checkpoint failed: {err}")))?;
+        management_store
+            .create_checkpoint(meta_db_dir)
+            .map_err(|err| ConsensusError::GeneralOwned(format!("meta DB checkpoint failed: {err}")))?;
+        Ok(checkpoint_path)
+    }
 }
 
 /// Impl for test purposes
@@ -55,4 +71,8 @@ impl ConsensusCtl for Consensus {
     fn make_active(&self) {
         unimplemented!()
     }
+
+    fn create_checkpoint(&self, _consensus_db_root: &Path, _meta_db_dir: &Path) -> ConsensusResult<PathBuf> {
+        unimplemented!()
+    }
 }
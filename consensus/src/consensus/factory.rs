@@ -20,7 +20,13 @@ use karlsen_utils::mem_size::MemSizeEstimator;
 use parking_lot::RwLock;
 use rocksdb::WriteBatch;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, error::Error, fs, path::PathBuf, sync::Arc};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ConsensusEntry {
@@ -203,6 +209,11 @@ impl MultiConsensusManagementStore {
         self.entries.delete(DirectDbWriter::new(&self.db), entry.key.into())
     }
 
+    /// Creates a checkpoint of the management DB at `path`
+    pub fn create_checkpoint(&self, path: &Path) -> StoreResult<()> {
+        self.db.create_checkpoint(path)
+    }
+
     pub fn is_archival_node(&self) -> StoreResult<bool> {
         match self.metadata.read() {
             Ok(data) => Ok(data.is_archival_node),
@@ -1,6 +1,7 @@
-use rocksdb::{DBWithThreadMode, MultiThreaded};
+use crate::prelude::StoreResult;
+use rocksdb::{checkpoint::Checkpoint, DBWithThreadMode, MultiThreaded};
 use std::ops::{Deref, DerefMut};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub use conn_builder::ConnBuilder;
 use karlsen_utils::fd_budget::FDGuard;
@@ -17,6 +18,13 @@ impl DB {
     pub fn new(inner: DBWithThreadMode<MultiThreaded>, fd_guard: FDGuard) -> Self {
         Self { inner, _fd_guard: fd_guard }
     }
+
+    /// Creates a consistent point-in-time copy of this DB at `path` (which must not exist yet). SST files
+    /// are hard-linked when `path` is on the same filesystem, so this is cheap even for large DBs.
+    pub fn create_checkpoint(&self, path: impl AsRef<Path>) -> StoreResult<()> {
+        Checkpoint::new(&self.inner)?.create_checkpoint(path)?;
+        Ok(())
+    }
 }
 
 impl DerefMut for DB {
@@ -42,3 +50,25 @@ pub fn delete_db(db_dir: PathBuf) {
     let path = db_dir.to_str().unwrap();
     <DBWithThreadMode<MultiThreaded>>::destroy(&options, path).expect("DB is expected to be deletable");
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{create_temp_db, load_existing_db, prelude::ConnBuilder, utils::get_karlsen_tempdir};
+
+    #[test]
+    fn test_create_checkpoint() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        db.put(b"key", b"before").unwrap();
+
+        let checkpoint_dir = get_karlsen_tempdir();
+        let checkpoint_path = checkpoint_dir.path().join("checkpoint");
+        db.create_checkpoint(&checkpoint_path).unwrap();
+        // Writes following the checkpoint must not be visible in it
+        db.put(b"key", b"after").unwrap();
+
+        let (_checkpoint_lifetime, checkpoint_db) = load_existing_db!(&checkpoint_path, ConnBuilder::default().with_files_limit(10));
+        assert_eq!(checkpoint_db.get(b"key").unwrap().as_deref(), Some(&b"before"[..]));
+        // The target directory must not already exist
+        assert!(db.create_checkpoint(&checkpoint_path).is_err());
+    }
+}
@@ -61,6 +61,11 @@ pub enum DatabaseStorePrefixes {
     UtxoIndex = 192,
     UtxoIndexTips = 193,
     CirculatingSupply = 194,
+    TxIndex = 195,
+    TxIndexSink = 196,
+    AddressHistory = 197,
+    AddressHistoryChainBlocks = 198,
+    AddressHistorySink = 199,
 
     // ---- Separator ----
     /// Reserved as a separator
@@ -0,0 +1,104 @@
+use karlsen_consensus_core::{acceptance_data::AcceptanceData, tx::ScriptPublicKey, utxo::utxo_diff::UtxoDiff};
+use karlsen_consensusmanager::spawn_blocking;
+use karlsen_database::prelude::StoreResult;
+use karlsen_hashes::Hash;
+use parking_lot::RwLock;
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{
+    errors::AddressHistoryResult,
+    model::{AddressHistoryCursor, AddressHistoryPage},
+};
+
+///Address history index API targeted at retrieval calls.
+pub trait AddressHistoryApi: Send + Sync + Debug {
+    /// Retrieve a page of the history of a script public key, ordered by accepting DAA score.
+    ///
+    /// Entries are restricted to the inclusive `[start_daa_score, end_daa_score]` range when bounds are provided,
+    /// and start right after `cursor` when it is provided.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_address_history(
+        &self,
+        script_public_key: ScriptPublicKey,
+        start_daa_score: Option<u64>,
+        end_daa_score: Option<u64>,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage>;
+
+    /// Retrieve the chain block the address history index was last synced to.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_address_history_sink(&self) -> StoreResult<Hash>;
+
+    /// Checks if the address history index's db is synced with consensus.
+    ///
+    /// Note:
+    /// 1) Use a read lock when accessing this method
+    /// 2) due to potential sync-gaps is_synced is unreliable while consensus is actively resolving virtual states.
+    fn is_synced(&self) -> AddressHistoryResult<bool>;
+
+    /// Record the UTXOs spent by a virtual state change, so that a following chain update can attribute spends
+    /// to their script public keys without querying consensus.
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn record_utxo_diff(&mut self, utxo_diff: Arc<UtxoDiff>);
+
+    /// Update the address history index with the given virtual chain changes.
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn update(
+        &mut self,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressHistoryResult<()>;
+
+    /// Resync the address history index from the consensus db, starting at the last synced chain block.
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn resync(&mut self) -> AddressHistoryResult<()>;
+}
+
+/// Async proxy for the address history index
+#[derive(Debug, Clone)]
+pub struct AddressHistoryProxy {
+    inner: Arc<RwLock<dyn AddressHistoryApi>>,
+}
+
+impl AddressHistoryProxy {
+    pub fn new(inner: Arc<RwLock<dyn AddressHistoryApi>>) -> Self {
+        Self { inner }
+    }
+
+    pub async fn get_address_history(
+        self,
+        script_public_key: ScriptPublicKey,
+        start_daa_score: Option<u64>,
+        end_daa_score: Option<u64>,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        spawn_blocking(move || self.inner.read().get_address_history(script_public_key, start_daa_score, end_daa_score, cursor, limit))
+            .await
+            .unwrap()
+    }
+
+    pub async fn record_utxo_diff(self, utxo_diff: Arc<UtxoDiff>) {
+        spawn_blocking(move || self.inner.write().record_utxo_diff(utxo_diff)).await.unwrap()
+    }
+
+    pub async fn update(
+        self,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressHistoryResult<()> {
+        spawn_blocking(move || {
+            self.inner.write().update(added_chain_block_hashes, removed_chain_block_hashes, added_chain_blocks_acceptance_data)
+        })
+        .await
+        .unwrap()
+    }
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+use crate::IDENT;
+use karlsen_consensus_core::errors::consensus::ConsensusError;
+use karlsen_database::prelude::StoreError;
+
+/// Errors originating from the [`AddressHistoryIndex`](crate::AddressHistoryIndex).
+#[derive(Error, Debug)]
+pub enum AddressHistoryError {
+    #[error("[{IDENT}]: {0}")]
+    StoreAccessError(#[from] StoreError),
+
+    #[error("[{IDENT}]: {0}")]
+    ConsensusError(#[from] ConsensusError),
+
+    #[error("[{IDENT}]: invalid history cursor")]
+    InvalidCursor,
+}
+
+/// Results originating from the [`AddressHistoryIndex`](crate::AddressHistoryIndex).
+pub type AddressHistoryResult<T> = Result<T, AddressHistoryError>;
@@ -0,0 +1,103 @@
+use karlsen_consensus_core::tx::{TransactionId, TransactionOutpoint};
+use karlsen_hashes::{Hash, HASH_SIZE};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AddressHistoryError;
+
+/// Whether an [`AddressHistoryEntry`] credits or debits its script public key.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum AddressHistoryEntryKind {
+    /// An output paying to the script public key
+    Received = 0,
+    /// An input spending an output previously paid to the script public key
+    Spent = 1,
+}
+
+impl TryFrom<u8> for AddressHistoryEntryKind {
+    type Error = AddressHistoryError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Received),
+            1 => Ok(Self::Spent),
+            _ => Err(AddressHistoryError::InvalidCursor),
+        }
+    }
+}
+
+/// A single balance change of a script public key, as recorded by the [`AddressHistoryIndex`](crate::AddressHistoryIndex).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressHistoryEntry {
+    /// The transaction causing the change
+    pub transaction_id: TransactionId,
+    pub kind: AddressHistoryEntryKind,
+    /// The output index for received entries, the input index for spent entries
+    pub index: u32,
+    /// The outpoint created (received) or consumed (spent) by the transaction
+    pub outpoint: TransactionOutpoint,
+    pub amount: u64,
+    /// The chain block accepting the transaction
+    pub accepting_block_hash: Hash,
+    /// The DAA score of the accepting chain block
+    pub accepting_block_daa_score: u64,
+}
+
+impl AddressHistoryEntry {
+    pub fn cursor(&self) -> AddressHistoryCursor {
+        AddressHistoryCursor::new(self.accepting_block_daa_score, self.transaction_id, self.kind, self.index)
+    }
+}
+
+/// Size of an [`AddressHistoryCursor`] in bytes.
+pub const ADDRESS_HISTORY_CURSOR_SIZE: usize = size_of::<u64>() + HASH_SIZE + size_of::<u8>() + size_of::<u32>();
+
+/// The position of an [`AddressHistoryEntry`] within the history of its script public key.
+///
+/// Entries are ordered by accepting DAA score first, so the byte representation uses big endian integers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AddressHistoryCursor {
+    pub accepting_block_daa_score: u64,
+    pub transaction_id: TransactionId,
+    pub kind: AddressHistoryEntryKind,
+    pub index: u32,
+}
+
+impl AddressHistoryCursor {
+    pub fn new(accepting_block_daa_score: u64, transaction_id: TransactionId, kind: AddressHistoryEntryKind, index: u32) -> Self {
+        Self { accepting_block_daa_score, transaction_id, kind, index }
+    }
+
+    pub fn to_bytes(&self) -> [u8; ADDRESS_HISTORY_CURSOR_SIZE] {
+        let mut bytes = [0; ADDRESS_HISTORY_CURSOR_SIZE];
+        bytes[..8].copy_from_slice(&self.accepting_block_daa_score.to_be_bytes());
+        bytes[8..8 + HASH_SIZE].copy_from_slice(&self.transaction_id.as_bytes());
+        bytes[8 + HASH_SIZE] = self.kind as u8;
+        bytes[8 + HASH_SIZE + 1..].copy_from_slice(&self.index.to_be_bytes());
+        bytes
+    }
+}
+
+impl TryFrom<&[u8]> for AddressHistoryCursor {
+    type Error = AddressHistoryError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != ADDRESS_HISTORY_CURSOR_SIZE {
+            return Err(AddressHistoryError::InvalidCursor);
+        }
+        Ok(Self {
+            accepting_block_daa_score: u64::from_be_bytes(bytes[..8].try_into().unwrap()),
+            transaction_id: Hash::from_slice(&bytes[8..8 + HASH_SIZE]),
+            kind: bytes[8 + HASH_SIZE].try_into()?,
+            index: u32::from_be_bytes(bytes[8 + HASH_SIZE + 1..].try_into().unwrap()),
+        })
+    }
+}
+
+/// A page of the history of a script public key, ordered by accepting DAA score.
+#[derive(Clone, Debug, Default)]
+pub struct AddressHistoryPage {
+    pub entries: Vec<AddressHistoryEntry>,
+    /// The cursor to pass in order to query the next page, `None` if this is the last page
+    pub next_cursor: Option<AddressHistoryCursor>,
+}
@@ -0,0 +1,548 @@
+use crate::{
+    api::AddressHistoryApi,
+    errors::{AddressHistoryError, AddressHistoryResult},
+    model::{AddressHistoryCursor, AddressHistoryEntryKind, AddressHistoryPage},
+    stores::{
+        history::{AddressHistoryKey, CompactAddressHistoryEntry},
+        store_manager::Store,
+    },
+    IDENT,
+};
+use karlsen_consensus_core::{
+    acceptance_data::AcceptanceData,
+    api::ConsensusApi,
+    errors::consensus::ConsensusResult,
+    tx::{ScriptPublicKey, SignableTransaction, Transaction, TransactionIndexType, TransactionOutpoint},
+    utxo::utxo_diff::UtxoDiff,
+};
+use karlsen_consensusmanager::{ConsensusManager, ConsensusResetHandler};
+use karlsen_core::{info, trace, warn};
+use karlsen_database::prelude::{StoreError, StoreResult, DB};
+use karlsen_hashes::{Hash, ZERO_HASH};
+use parking_lot::RwLock;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+
+const RESYNC_CHUNK_SIZE: usize = 1024;
+
+/// The amount of recent UTXO diffs kept in memory for resolving the UTXOs spent by newly accepted transactions.
+const RECORDED_UTXO_DIFFS_CAPACITY: usize = 64;
+
+/// AddressHistoryIndex records every output paid to and every input spending from a script public key,
+/// for all transactions accepted by the selected chain, and commits them to its own store.
+///
+/// Received entries are built from the outputs of accepted transactions. Spent entries are resolved from the
+/// UTXO diffs of the virtual state changes, or from the UTXO diffs consensus keeps for its chain blocks.
+/// Note: The AddressHistoryIndex struct by itself is not thread save, only correct usage of the supplied RwLock via `new` makes it so.
+/// please follow guidelines found in the comments under `addresshistory::core::api::AddressHistoryApi` for proper thread safety.
+pub struct AddressHistoryIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+    recorded_utxo_diffs: VecDeque<Arc<UtxoDiff>>,
+}
+
+impl AddressHistoryIndex {
+    /// Creates a new [`AddressHistoryIndex`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> AddressHistoryResult<Arc<RwLock<Self>>> {
+        let mut index =
+            Self { consensus_manager: consensus_manager.clone(), store: Store::new(db), recorded_utxo_diffs: VecDeque::new() };
+        if !index.is_synced()? {
+            index.resync()?;
+        }
+        let index = Arc::new(RwLock::new(index));
+        consensus_manager.register_consensus_reset_handler(Arc::new(AddressHistoryConsensusResetHandler::new(Arc::downgrade(&index))));
+        Ok(index)
+    }
+
+    /// Builds the history entries of all transactions accepted by a chain block.
+    fn collect_chain_block_entries(
+        &self,
+        consensus: &dyn ConsensusApi,
+        accepting_block_hash: Hash,
+        acceptance_data: &AcceptanceData,
+    ) -> AddressHistoryResult<Vec<(AddressHistoryKey, CompactAddressHistoryEntry)>> {
+        let accepting_block_daa_score = consensus.get_header(accepting_block_hash)?.daa_score;
+        let bodies = acceptance_data
+            .iter()
+            .map(|mergeset_block_data| consensus.get_block_body(mergeset_block_data.block_hash))
+            .collect::<ConsensusResult<Vec<_>>>()?;
+        let transactions: Vec<(Hash, &Transaction)> = acceptance_data
+            .iter()
+            .zip(bodies.iter())
+            .flat_map(|(mergeset_block_data, body)| {
+                mergeset_block_data.accepted_transactions.iter().map(|tx| (tx.transaction_id, &body[tx.index_within_block as usize]))
+            })
+            .collect();
+
+        let mut entries = Vec::new();
+        let mut push_entry = |script_public_key: &ScriptPublicKey,
+                              transaction_id: Hash,
+                              kind: AddressHistoryEntryKind,
+                              index: TransactionIndexType,
+                              outpoint: TransactionOutpoint,
+                              amount: u64| {
+            let cursor = AddressHistoryCursor::new(accepting_block_daa_score, transaction_id, kind, index);
+            entries.push((
+                AddressHistoryKey::new(script_public_key, &cursor),
+                CompactAddressHistoryEntry { outpoint, amount, accepting_block_hash },
+            ));
+        };
+
+        // Outputs created by the chain block may be spent by other transactions accepted by the same chain block,
+        // in which case they never show up in any UTXO diff.
+        let mut created = HashMap::new();
+        for (transaction_id, tx) in transactions.iter().copied() {
+            for (index, output) in tx.outputs.iter().enumerate() {
+                let outpoint = TransactionOutpoint::new(transaction_id, index as TransactionIndexType);
+                push_entry(
+                    &output.script_public_key,
+                    transaction_id,
+                    AddressHistoryEntryKind::Received,
+                    outpoint.index,
+                    outpoint,
+                    output.value,
+                );
+                created.insert(outpoint, output);
+            }
+        }
+
+        for (transaction_id, tx) in transactions.iter().copied().filter(|(_, tx)| !tx.is_coinbase()) {
+            let mut populated_tx: Option<SignableTransaction> = None;
+            for (index, input) in tx.inputs.iter().enumerate() {
+                let outpoint = input.previous_outpoint;
+                let spent = match created.get(&outpoint) {
+                    Some(output) => Some((output.script_public_key.clone(), output.value)),
+                    None => match self.recorded_utxo_diffs.iter().rev().find_map(|utxo_diff| utxo_diff.remove.get(&outpoint)) {
+                        Some(entry) => Some((entry.script_public_key.clone(), entry.amount)),
+                        None => {
+                            // Fall back to the UTXO diff stored by consensus for the accepting chain block
+                            if populated_tx.is_none() {
+                                populated_tx = consensus
+                                    .get_populated_transaction(transaction_id, accepting_block_daa_score)
+                                    .inspect_err(|err| {
+                                        warn!("[{0}] cannot populate the inputs of transaction {1}: {2}", IDENT, transaction_id, err)
+                                    })
+                                    .ok();
+                            }
+                            populated_tx
+                                .as_ref()
+                                .and_then(|populated_tx| populated_tx.entries[index].as_ref())
+                                .map(|entry| (entry.script_public_key.clone(), entry.amount))
+                        }
+                    },
+                };
+                match spent {
+                    Some((script_public_key, amount)) => push_entry(
+                        &script_public_key,
+                        transaction_id,
+                        AddressHistoryEntryKind::Spent,
+                        index as TransactionIndexType,
+                        outpoint,
+                        amount,
+                    ),
+                    None => warn!("[{0}] cannot resolve the UTXO spent by input {1} of transaction {2}", IDENT, index, transaction_id),
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Applies a selected chain change to the store:
+    /// 1) removes the entries contributed by the removed chain blocks.
+    /// 2) adds the entries of all transactions accepted by the added chain blocks.
+    /// 3) commits the last added chain block as the address history sink.
+    fn apply_chain_changes(
+        &mut self,
+        consensus: &dyn ConsensusApi,
+        added_chain_block_hashes: &[Hash],
+        removed_chain_block_hashes: &[Hash],
+        added_chain_blocks_acceptance_data: &[Arc<AcceptanceData>],
+    ) -> AddressHistoryResult<()> {
+        for removed_block in removed_chain_block_hashes.iter().copied() {
+            trace!("[{0}] removing the entries of chain block {1}", IDENT, removed_block);
+            self.store.remove_chain_block_entries(removed_block)?;
+        }
+
+        for (accepting_block_hash, acceptance_data) in added_chain_block_hashes.iter().copied().zip(added_chain_blocks_acceptance_data)
+        {
+            let entries = self.collect_chain_block_entries(consensus, accepting_block_hash, acceptance_data)?;
+            trace!("[{0}] adding {1} entries of chain block {2}", IDENT, entries.len(), accepting_block_hash);
+            self.store.add_chain_block_entries(accepting_block_hash, entries)?;
+        }
+
+        if let Some(sink) = added_chain_block_hashes.last().copied() {
+            self.store.set_sink(sink)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl AddressHistoryApi for AddressHistoryIndex {
+    fn get_address_history(
+        &self,
+        script_public_key: ScriptPublicKey,
+        start_daa_score: Option<u64>,
+        end_daa_score: Option<u64>,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        trace!("[{0}] retrieving address history", IDENT);
+
+        let start =
+            start_daa_score.map(|daa_score| AddressHistoryCursor::new(daa_score, ZERO_HASH, AddressHistoryEntryKind::Received, 0));
+        // The entry at a resumed cursor was already returned by the previous page and has to be skipped
+        let (seek_from, skipped) = match (cursor, start) {
+            (Some(cursor), Some(start)) if start > cursor => (Some(start), None),
+            (Some(cursor), _) => (Some(cursor), Some(cursor)),
+            (None, start) => (start, None),
+        };
+
+        // One extra entry is fetched to detect the existence of a next page
+        let mut entries = self.store.get_entries(&script_public_key, seek_from, end_daa_score, limit.saturating_add(2))?;
+        if skipped.is_some_and(|skipped| entries.first().is_some_and(|entry| entry.cursor() == skipped)) {
+            entries.remove(0);
+        }
+        let next_cursor = if entries.len() > limit {
+            entries.truncate(limit);
+            entries.last().map(|entry| entry.cursor())
+        } else {
+            None
+        };
+        Ok(AddressHistoryPage { entries, next_cursor })
+    }
+
+    fn get_address_history_sink(&self) -> StoreResult<Hash> {
+        trace!("[{0}] retrieving sink", IDENT);
+
+        self.store.get_sink()
+    }
+
+    /// Checks to see if the [AddressHistoryIndex] is sync'd. This is done via comparing the committed sink with the one of the consensus database.
+    ///
+    /// **Note:** Due to sync gaps between the index and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_synced(&self) -> AddressHistoryResult<bool> {
+        trace!("[{0}] checking sync status...", IDENT);
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        match self.store.get_sink() {
+            Ok(sink) => {
+                let res = sink == session.get_sink();
+                trace!("[{0}] sync status is {1}", IDENT, res);
+                Ok(res)
+            }
+            Err(StoreError::KeyNotFound(_)) => {
+                // Means the sink is empty i.e. not sync'd.
+                trace!("[{0}] sync status is {1}", IDENT, false);
+                Ok(false)
+            }
+            Err(other_store_errors) => Err(AddressHistoryError::StoreAccessError(other_store_errors)),
+        }
+    }
+
+    fn record_utxo_diff(&mut self, utxo_diff: Arc<UtxoDiff>) {
+        trace!("[{0}] recording a utxo diff with {1} removed utxos", IDENT, utxo_diff.remove.len());
+
+        if self.recorded_utxo_diffs.len() == RECORDED_UTXO_DIFFS_CAPACITY {
+            self.recorded_utxo_diffs.pop_front();
+        }
+        self.recorded_utxo_diffs.push_back(utxo_diff);
+    }
+
+    /// Updates the [AddressHistoryIndex] via the virtual chain changes supplied.
+    fn update(
+        &mut self,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressHistoryResult<()> {
+        trace!("[{0}] updating...", IDENT);
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+        self.apply_chain_changes(
+            &*session,
+            &added_chain_block_hashes,
+            &removed_chain_block_hashes,
+            &added_chain_blocks_acceptance_data,
+        )
+    }
+
+    /// Syncs the [AddressHistoryIndex] with the selected chain of the consensus database.
+    ///
+    /// Walks the chain from the last committed sink if consensus still knows it, otherwise from the retention period root.
+    /// Existing entries are kept in both cases so that the history outlives consensus pruning and resets.
+    ///
+    /// **Note:** resyncing while consensus notifies of virtual chain changes, may result in a corrupted db.
+    fn resync(&mut self) -> AddressHistoryResult<()> {
+        info!("Resyncing the address history index...");
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let mut low = match self.store.get_sink() {
+            Ok(sink) if session.get_virtual_chain_from_block(sink, Some(0)).is_ok() => sink,
+            Ok(_) | Err(StoreError::KeyNotFound(_)) => {
+                // Either the index is empty or its sink is unknown to the current consensus (e.g. following a consensus reset),
+                // so we start over from the earliest block having full data, including its own acceptance data.
+                let retention_period_root = session.get_retention_period_root();
+                if let Ok(acceptance_data) = session.get_block_acceptance_data(retention_period_root) {
+                    self.apply_chain_changes(&*session, &[retention_period_root], &[], &[acceptance_data])?;
+                }
+                self.store.set_sink(retention_period_root)?;
+                retention_period_root
+            }
+            Err(other_store_errors) => return Err(AddressHistoryError::StoreAccessError(other_store_errors)),
+        };
+
+        loop {
+            let chain_path = session.get_virtual_chain_from_block(low, Some(RESYNC_CHUNK_SIZE))?;
+            trace!("[{0}] resyncing with a batch of {1} chain blocks from consensus db", IDENT, chain_path.added.len());
+            let acceptance_data =
+                chain_path.added.iter().map(|hash| session.get_block_acceptance_data(*hash)).collect::<ConsensusResult<Vec<_>>>()?;
+            self.apply_chain_changes(&*session, &chain_path.added, &chain_path.removed, &acceptance_data)?;
+
+            match chain_path.added.last() {
+                Some(last) if chain_path.added.len() == RESYNC_CHUNK_SIZE => low = *last,
+                _ => break,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for AddressHistoryIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AddressHistoryIndex").finish()
+    }
+}
+
+struct AddressHistoryConsensusResetHandler {
+    index: Weak<RwLock<AddressHistoryIndex>>,
+}
+
+impl AddressHistoryConsensusResetHandler {
+    fn new(index: Weak<RwLock<AddressHistoryIndex>>) -> Self {
+        Self { index }
+    }
+}
+
+impl ConsensusResetHandler for AddressHistoryConsensusResetHandler {
+    fn handle_consensus_reset(&self) {
+        if let Some(index) = self.index.upgrade() {
+            index.write().resync().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::AddressHistoryApi,
+        model::{AddressHistoryCursor, AddressHistoryEntryKind},
+        AddressHistoryIndex,
+    };
+    use karlsen_consensus::{
+        config::ConfigBuilder,
+        consensus::test_consensus::TestConsensus,
+        params::{ForkActivation, DEVNET_PARAMS},
+    };
+    use karlsen_consensus_core::{
+        api::{args::TransactionValidationArgs, ConsensusApi},
+        blockstatus::BlockStatus,
+        coinbase::MinerData,
+        subnets::SubnetworkId,
+        tx::{
+            MutableTransaction, ScriptPublicKey, Transaction, TransactionIndexType, TransactionInput, TransactionOutpoint,
+            TransactionOutput,
+        },
+    };
+    use karlsen_consensusmanager::ConsensusManager;
+    use karlsen_database::create_temp_db;
+    use karlsen_database::prelude::ConnBuilder;
+    use karlsen_hashes::Hash;
+    use karlsen_utils::arc::ArcExtensions;
+    use std::sync::Arc;
+
+    /// Lists the cursors of all outputs accepted by the given chain blocks, in history order.
+    fn expected_received_cursors(tc: &TestConsensus, chain: &[Hash]) -> Vec<AddressHistoryCursor> {
+        let mut cursors = Vec::new();
+        for accepting_block_hash in chain.iter().copied() {
+            let daa_score = tc.get_header(accepting_block_hash).unwrap().daa_score;
+            let Ok(acceptance_data) = tc.get_block_acceptance_data(accepting_block_hash) else {
+                continue;
+            };
+            for mergeset_block_data in acceptance_data.iter() {
+                let body = tc.get_block_body(mergeset_block_data.block_hash).unwrap();
+                for tx in mergeset_block_data.accepted_transactions.iter() {
+                    let outputs = body[tx.index_within_block as usize].outputs.len();
+                    cursors.extend((0..outputs).map(|index| {
+                        AddressHistoryCursor::new(
+                            daa_score,
+                            tx.transaction_id,
+                            AddressHistoryEntryKind::Received,
+                            index as TransactionIndexType,
+                        )
+                    }));
+                }
+            }
+        }
+        cursors.sort();
+        cursors
+    }
+
+    #[tokio::test]
+    async fn test_address_history() {
+        karlsen_core::log::try_init_logger("INFO");
+
+        let (_db_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let config = ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build();
+        let tc = Arc::new(TestConsensus::new(&config));
+        let wait_handles = tc.init();
+        // The miner script public key used by `add_utxo_valid_block_with_parents`
+        let script_public_key = ScriptPublicKey::from_vec(0, vec![]);
+
+        // Build an initial selected chain
+        let mut chain = vec![config.genesis.hash];
+        for i in 1..=10u64 {
+            let hash: Hash = i.into();
+            tc.add_utxo_valid_block_with_parents(hash, vec![*chain.last().unwrap()], vec![]).await.unwrap();
+            chain.push(hash);
+        }
+
+        // Sync the index from scratch.
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+        let index = AddressHistoryIndex::new(consensus_manager, db).unwrap();
+        assert!(index.read().is_synced().unwrap());
+        let expected = expected_received_cursors(&tc, &chain);
+        assert!(!expected.is_empty());
+
+        // Walk the full history page by page
+        let mut cursors = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = index.read().get_address_history(script_public_key.clone(), None, None, cursor, 3).unwrap();
+            assert!(page.entries.len() <= 3);
+            assert!(page.entries.iter().all(|entry| entry.amount > 0 && chain.contains(&entry.accepting_block_hash)));
+            cursors.extend(page.entries.iter().map(|entry| entry.cursor()));
+            match page.next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => break,
+            }
+        }
+        assert_eq!(cursors, expected);
+
+        // Filter by a DAA score range
+        let (start, end) = (expected[1].accepting_block_daa_score, expected[expected.len() - 2].accepting_block_daa_score);
+        let page = index.read().get_address_history(script_public_key.clone(), Some(start), Some(end), None, usize::MAX).unwrap();
+        let in_range: Vec<_> = expected.iter().copied().filter(|c| (start..=end).contains(&c.accepting_block_daa_score)).collect();
+        assert_eq!(page.entries.iter().map(|entry| entry.cursor()).collect::<Vec<_>>(), in_range);
+        assert!(page.next_cursor.is_none());
+
+        // Reorg to a longer chain forking from genesis and resync from the previous sink.
+        let mut fork = vec![config.genesis.hash];
+        for i in 11..=22u64 {
+            let hash: Hash = i.into();
+            tc.add_utxo_valid_block_with_parents(hash, vec![*fork.last().unwrap()], vec![]).await.unwrap();
+            fork.push(hash);
+        }
+        assert_eq!(tc.get_sink(), *fork.last().unwrap());
+        assert!(!index.read().is_synced().unwrap());
+        index.write().resync().unwrap();
+        assert!(index.read().is_synced().unwrap());
+
+        // Only the entries of the new chain are left
+        let page = index.read().get_address_history(script_public_key, None, None, None, usize::MAX).unwrap();
+        assert_eq!(page.entries.iter().map(|entry| entry.cursor()).collect::<Vec<_>>(), expected_received_cursors(&tc, &fork));
+
+        // Deconstruct
+        drop(index);
+        tc.shutdown(wait_handles);
+    }
+
+    #[tokio::test]
+    async fn test_address_history_spent_entries() {
+        karlsen_core::log::try_init_logger("INFO");
+
+        let (_db_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let config = ConfigBuilder::new(DEVNET_PARAMS)
+            .skip_proof_of_work()
+            .edit_consensus_params(|p| {
+                p.prior_coinbase_maturity = 0;
+                p.crescendo.coinbase_maturity = 0;
+                p.crescendo_activation = ForkActivation::always()
+            })
+            .build();
+        let tc = Arc::new(TestConsensus::new(&config));
+        let wait_handles = tc.init();
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+        let index = AddressHistoryIndex::new(consensus_manager, db).unwrap();
+
+        // Fund an anyone-can-spend script public key with a coinbase output
+        let funded_script_public_key = ScriptPublicKey::from_vec(0, vec![karlsen_txscript::opcodes::codes::OpTrue]);
+        let miner_data = MinerData::new(funded_script_public_key.clone(), vec![]);
+        let block = tc.build_utxo_valid_block_with_parents(1.into(), vec![config.genesis.hash], miner_data.clone(), vec![]);
+        tc.validate_and_insert_block(block.to_immutable()).virtual_state_task.await.unwrap();
+        let funding_block = tc.build_utxo_valid_block_with_parents(2.into(), vec![1.into()], miner_data, vec![]);
+        let mut coinbase = funding_block.transactions[0].clone();
+        coinbase.finalize();
+        tc.validate_and_insert_block(funding_block.to_immutable()).virtual_state_task.await.unwrap();
+
+        // Spend it in a block accepted by a later chain block than the one accepting the coinbase
+        let outpoint = TransactionOutpoint::new(coinbase.id(), 0);
+        let amount = coinbase.outputs[0].value;
+        let recipient_script_public_key = ScriptPublicKey::from_vec(0, vec![]);
+        let tx = Transaction::new(
+            0,
+            vec![TransactionInput::new(outpoint, vec![], 0, 0)],
+            vec![TransactionOutput::new(amount, recipient_script_public_key.clone())],
+            0,
+            SubnetworkId::default(),
+            0,
+            vec![],
+        );
+        let mut tx = MutableTransaction::from_tx(tx);
+        // This triggers storage mass population
+        tc.validate_mempool_transaction(&mut tx, &TransactionValidationArgs::default()).unwrap();
+        let tx = tx.tx.unwrap_or_clone();
+        let status = tc.add_utxo_valid_block_with_parents(3.into(), vec![2.into()], vec![tx.clone()]).await;
+        assert!(matches!(status, Ok(BlockStatus::StatusUTXOValid)));
+        tc.add_utxo_valid_block_with_parents(4.into(), vec![3.into()], vec![]).await.unwrap();
+        let spending_daa_score = tc.get_header(4.into()).unwrap().daa_score;
+
+        // The index starts out of sync, so the spent UTXO is resolved from the UTXO diffs stored by consensus
+        assert!(!index.read().is_synced().unwrap());
+        index.write().resync().unwrap();
+
+        let page = index.read().get_address_history(funded_script_public_key, None, None, None, usize::MAX).unwrap();
+        let received = page.entries.iter().find(|entry| entry.outpoint == outpoint).expect("expected the funding entry");
+        assert_eq!(received.kind, AddressHistoryEntryKind::Received);
+        assert_eq!(received.transaction_id, coinbase.id());
+        assert_eq!(received.amount, amount);
+        // The coinbase of block 3 pays the funded script public key as well, so the spent entry is looked up by kind
+        let spent = page.entries.iter().find(|entry| entry.kind == AddressHistoryEntryKind::Spent).expect("expected the spent entry");
+        assert_eq!(spent.transaction_id, tx.id());
+        assert_eq!(spent.index, 0);
+        assert_eq!(spent.outpoint, outpoint);
+        assert_eq!(spent.amount, amount);
+        assert_eq!(spent.accepting_block_hash, 4.into());
+        assert_eq!(spent.accepting_block_daa_score, spending_daa_score);
+
+        let page = index.read().get_address_history(recipient_script_public_key, None, None, None, usize::MAX).unwrap();
+        let received = page.entries.iter().find(|entry| entry.transaction_id == tx.id()).expect("expected the payment entry");
+        assert_eq!(received.kind, AddressHistoryEntryKind::Received);
+        assert_eq!(received.outpoint, TransactionOutpoint::new(tx.id(), 0));
+        assert_eq!(received.amount, amount);
+
+        // Deconstruct
+        drop(index);
+        tc.shutdown(wait_handles);
+    }
+}
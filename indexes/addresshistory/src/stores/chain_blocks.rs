@@ -0,0 +1,57 @@
+use crate::stores::history::AddressHistoryKey;
+
+use karlsen_database::prelude::{CachePolicy, CachedDbAccess, DirectDbWriter, StoreError, StoreResult, DB};
+use karlsen_database::registry::DatabaseStorePrefixes;
+use karlsen_hashes::Hash;
+use std::sync::Arc;
+
+// Traits:
+
+pub trait ChainBlockHistoryKeysStoreReader {
+    /// Get the keys of the history entries recorded for the transactions accepted by a chain block, if any.
+    fn get(&self, accepting_block_hash: Hash) -> StoreResult<Option<Arc<Vec<AddressHistoryKey>>>>;
+}
+
+pub trait ChainBlockHistoryKeysStore: ChainBlockHistoryKeysStoreReader {
+    fn insert(&mut self, accepting_block_hash: Hash, keys: Arc<Vec<AddressHistoryKey>>) -> StoreResult<()>;
+
+    fn delete(&mut self, accepting_block_hash: Hash) -> StoreResult<()>;
+}
+
+// Implementations:
+
+/// Tracks the history entries contributed by every indexed chain block, so that they can be reverted on reorgs.
+#[derive(Clone)]
+pub struct DbChainBlockHistoryKeysStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<Hash, Arc<Vec<AddressHistoryKey>>>,
+}
+
+impl DbChainBlockHistoryKeysStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AddressHistoryChainBlocks.into()),
+        }
+    }
+}
+
+impl ChainBlockHistoryKeysStoreReader for DbChainBlockHistoryKeysStore {
+    fn get(&self, accepting_block_hash: Hash) -> StoreResult<Option<Arc<Vec<AddressHistoryKey>>>> {
+        match self.access.read(accepting_block_hash) {
+            Ok(keys) => Ok(Some(keys)),
+            Err(StoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl ChainBlockHistoryKeysStore for DbChainBlockHistoryKeysStore {
+    fn insert(&mut self, accepting_block_hash: Hash, keys: Arc<Vec<AddressHistoryKey>>) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), accepting_block_hash, keys)
+    }
+
+    fn delete(&mut self, accepting_block_hash: Hash) -> StoreResult<()> {
+        self.access.delete(DirectDbWriter::new(&self.db), accepting_block_hash)
+    }
+}
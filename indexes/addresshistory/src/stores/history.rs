@@ -0,0 +1,152 @@
+use crate::core::model::{AddressHistoryCursor, AddressHistoryEntry};
+
+use karlsen_consensus_core::tx::{ScriptPublicKey, ScriptPublicKeyVersion, TransactionOutpoint};
+use karlsen_database::prelude::{CachePolicy, CachedDbAccess, DirectDbWriter, StoreResult, DB};
+use karlsen_database::registry::DatabaseStorePrefixes;
+use karlsen_hashes::Hash;
+use karlsen_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub const VERSION_TYPE_SIZE: usize = size_of::<ScriptPublicKeyVersion>();
+
+/// [`ScriptPublicKeyBucket`].
+/// Consists of 2 bytes of little endian [ScriptPublicKeyVersion] bytes, followed by 8 bytes of little endian script length
+/// and a variable size of script bytes.
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+struct ScriptPublicKeyBucket(Vec<u8>);
+
+impl From<&ScriptPublicKey> for ScriptPublicKeyBucket {
+    fn from(script_public_key: &ScriptPublicKey) -> Self {
+        let mut bytes: Vec<u8> = Vec::with_capacity(VERSION_TYPE_SIZE + size_of::<u64>() + script_public_key.script().len());
+        bytes.extend_from_slice(&script_public_key.version().to_le_bytes());
+        bytes.extend_from_slice(&(script_public_key.script().len() as u64).to_le_bytes());
+        bytes.extend_from_slice(script_public_key.script());
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for ScriptPublicKeyBucket {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+/// Full [CompactAddressHistoryEntry] access key.
+/// Consists of variable amount of bytes of [ScriptPublicKeyBucket], followed by the bytes of an [AddressHistoryCursor]
+#[derive(Eq, Hash, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct AddressHistoryKey(Arc<Vec<u8>>);
+
+impl AddressHistoryKey {
+    pub fn new(script_public_key: &ScriptPublicKey, cursor: &AddressHistoryCursor) -> Self {
+        Self::from_bucket(&ScriptPublicKeyBucket::from(script_public_key), &cursor.to_bytes())
+    }
+
+    fn from_bucket(bucket: &ScriptPublicKeyBucket, suffix: &[u8]) -> Self {
+        let mut bytes = Vec::with_capacity(bucket.as_ref().len() + suffix.len());
+        bytes.extend_from_slice(bucket.as_ref());
+        bytes.extend_from_slice(suffix);
+        Self(Arc::new(bytes))
+    }
+}
+
+impl AsRef<[u8]> for AddressHistoryKey {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+impl MemSizeEstimator for AddressHistoryKey {}
+
+/// The part of an [AddressHistoryEntry] which is not encoded in its [AddressHistoryKey]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CompactAddressHistoryEntry {
+    pub outpoint: TransactionOutpoint,
+    pub amount: u64,
+    pub accepting_block_hash: Hash,
+}
+
+impl MemSizeEstimator for CompactAddressHistoryEntry {}
+
+// Traits:
+
+pub trait AddressHistoryStoreReader {
+    /// Get up to `limit` [AddressHistoryEntry]s of a script public key, starting at `seek_from` (inclusive)
+    /// and ending at `end_daa_score` (inclusive).
+    fn get_entries(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        seek_from: Option<AddressHistoryCursor>,
+        end_daa_score: Option<u64>,
+        limit: usize,
+    ) -> StoreResult<Vec<AddressHistoryEntry>>;
+}
+
+pub trait AddressHistoryStore: AddressHistoryStoreReader {
+    /// add [CompactAddressHistoryEntry]s into the [AddressHistoryStore].
+    fn add_entries(&mut self, entries: &[(AddressHistoryKey, CompactAddressHistoryEntry)]) -> StoreResult<()>;
+
+    /// remove the [CompactAddressHistoryEntry]s matching the given keys from the [AddressHistoryStore].
+    fn remove_entries(&mut self, keys: &[AddressHistoryKey]) -> StoreResult<()>;
+}
+
+// Implementations:
+
+#[derive(Clone)]
+pub struct DbAddressHistoryStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<AddressHistoryKey, CompactAddressHistoryEntry>,
+}
+
+impl DbAddressHistoryStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AddressHistory.into()) }
+    }
+}
+
+impl AddressHistoryStoreReader for DbAddressHistoryStore {
+    fn get_entries(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        seek_from: Option<AddressHistoryCursor>,
+        end_daa_score: Option<u64>,
+        limit: usize,
+    ) -> StoreResult<Vec<AddressHistoryEntry>> {
+        let bucket = ScriptPublicKeyBucket::from(script_public_key);
+        let seek_key = seek_from.map(|cursor| AddressHistoryKey::from_bucket(&bucket, &cursor.to_bytes()));
+        let mut entries = Vec::new();
+        for res in self.access.seek_iterator(Some(bucket.as_ref()), seek_key, limit, false) {
+            let (key, entry) = res.unwrap();
+            let cursor = AddressHistoryCursor::try_from(&key[..]).expect("expected a key of size ADDRESS_HISTORY_CURSOR_SIZE");
+            if end_daa_score.is_some_and(|end_daa_score| cursor.accepting_block_daa_score > end_daa_score) {
+                break;
+            }
+            entries.push(AddressHistoryEntry {
+                transaction_id: cursor.transaction_id,
+                kind: cursor.kind,
+                index: cursor.index,
+                outpoint: entry.outpoint,
+                amount: entry.amount,
+                accepting_block_hash: entry.accepting_block_hash,
+                accepting_block_daa_score: cursor.accepting_block_daa_score,
+            });
+        }
+        Ok(entries)
+    }
+}
+
+impl AddressHistoryStore for DbAddressHistoryStore {
+    fn add_entries(&mut self, entries: &[(AddressHistoryKey, CompactAddressHistoryEntry)]) -> StoreResult<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        self.access.write_many(DirectDbWriter::new(&self.db), &mut entries.iter().cloned())
+    }
+
+    fn remove_entries(&mut self, keys: &[AddressHistoryKey]) -> StoreResult<()> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        self.access.delete_many(DirectDbWriter::new(&self.db), &mut keys.iter().cloned())
+    }
+}
@@ -0,0 +1,4 @@
+mod chain_blocks;
+pub mod history;
+mod sink;
+pub mod store_manager;
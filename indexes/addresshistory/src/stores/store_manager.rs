@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use karlsen_consensus_core::tx::ScriptPublicKey;
+use karlsen_database::prelude::{CachePolicy, StoreResult, DB};
+use karlsen_hashes::Hash;
+
+use crate::{
+    model::{AddressHistoryCursor, AddressHistoryEntry},
+    stores::{
+        chain_blocks::{ChainBlockHistoryKeysStore, ChainBlockHistoryKeysStoreReader, DbChainBlockHistoryKeysStore},
+        history::{
+            AddressHistoryKey, AddressHistoryStore, AddressHistoryStoreReader, CompactAddressHistoryEntry, DbAddressHistoryStore,
+        },
+        sink::{AddressHistorySinkStore, AddressHistorySinkStoreReader, DbAddressHistorySinkStore},
+    },
+};
+
+#[derive(Clone)]
+pub struct Store {
+    address_history_sink_store: DbAddressHistorySinkStore,
+    address_history_store: DbAddressHistoryStore,
+    chain_block_keys_store: DbChainBlockHistoryKeysStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            address_history_sink_store: DbAddressHistorySinkStore::new(db.clone()),
+            // History entries are only ever read through range queries, so caching them is pointless
+            address_history_store: DbAddressHistoryStore::new(db.clone(), CachePolicy::Empty),
+            chain_block_keys_store: DbChainBlockHistoryKeysStore::new(db, CachePolicy::Count(1_000)),
+        }
+    }
+
+    pub fn get_entries(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        seek_from: Option<AddressHistoryCursor>,
+        end_daa_score: Option<u64>,
+        limit: usize,
+    ) -> StoreResult<Vec<AddressHistoryEntry>> {
+        self.address_history_store.get_entries(script_public_key, seek_from, end_daa_score, limit)
+    }
+
+    /// Adds the history entries contributed by a chain block.
+    pub fn add_chain_block_entries(
+        &mut self,
+        accepting_block_hash: Hash,
+        entries: Vec<(AddressHistoryKey, CompactAddressHistoryEntry)>,
+    ) -> StoreResult<()> {
+        self.address_history_store.add_entries(&entries)?;
+        self.chain_block_keys_store.insert(accepting_block_hash, Arc::new(entries.into_iter().map(|(key, _)| key).collect()))
+    }
+
+    /// Removes the history entries contributed by a chain block, if any.
+    pub fn remove_chain_block_entries(&mut self, accepting_block_hash: Hash) -> StoreResult<()> {
+        if let Some(keys) = self.chain_block_keys_store.get(accepting_block_hash)? {
+            self.address_history_store.remove_entries(&keys)?;
+            self.chain_block_keys_store.delete(accepting_block_hash)?;
+        }
+        Ok(())
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.address_history_sink_store.get()
+    }
+
+    pub fn set_sink(&mut self, sink: Hash) -> StoreResult<()> {
+        self.address_history_sink_store.set(sink)
+    }
+}
@@ -1,4 +1,6 @@
+use karlsen_addresshistory::errors::AddressHistoryError;
 use karlsen_notify::events::EventType;
+use karlsen_txindex::errors::TxIndexError;
 use karlsen_utxoindex::errors::UtxoIndexError;
 use thiserror::Error;
 
@@ -7,6 +9,12 @@ pub enum IndexError {
     #[error("{0}")]
     UtxoIndexError(#[from] UtxoIndexError),
 
+    #[error("{0}")]
+    TxIndexError(#[from] TxIndexError),
+
+    #[error("{0}")]
+    AddressHistoryError(#[from] AddressHistoryError),
+
     #[error("event type {0:?} is not supported")]
     NotSupported(EventType),
 }
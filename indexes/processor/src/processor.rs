@@ -3,6 +3,7 @@ use crate::{
     IDENT,
 };
 use async_trait::async_trait;
+use karlsen_addresshistory::api::AddressHistoryProxy;
 use karlsen_consensus_notify::{notification as consensus_notification, notification::Notification as ConsensusNotification};
 use karlsen_core::{debug, trace};
 use karlsen_index_core::notification::{Notification, PruningPointUtxoSetOverrideNotification, UtxosChangedNotification};
@@ -13,6 +14,7 @@ use karlsen_notify::{
     notification::Notification as NotificationTrait,
     notifier::DynNotify,
 };
+use karlsen_txindex::api::TxIndexProxy;
 use karlsen_utils::triggers::SingleTrigger;
 use karlsen_utxoindex::api::UtxoIndexProxy;
 use std::sync::{
@@ -21,7 +23,9 @@ use std::sync::{
 };
 
 /// Processor processes incoming consensus UtxosChanged and PruningPointUtxoSetOverride
-/// notifications submitting them to a UtxoIndex.
+/// notifications submitting them to a UtxoIndex, and VirtualChainChanged notifications
+/// submitting them to a TxIndex. Both UtxosChanged and VirtualChainChanged notifications
+/// are submitted to an AddressHistoryIndex.
 ///
 /// It also acts as a [`Collector`], converting the incoming consensus notifications
 /// into their pending local versions and relaying them to a local notifier.
@@ -30,6 +34,12 @@ pub struct Processor {
     /// An optional UTXO indexer
     utxoindex: Option<UtxoIndexProxy>,
 
+    /// An optional transaction indexer
+    txindex: Option<TxIndexProxy>,
+
+    /// An optional address history indexer
+    addresshistory: Option<AddressHistoryProxy>,
+
     recv_channel: CollectorNotificationReceiver<ConsensusNotification>,
 
     /// Has this collector been started?
@@ -39,9 +49,16 @@ pub struct Processor {
 }
 
 impl Processor {
-    pub fn new(utxoindex: Option<UtxoIndexProxy>, recv_channel: CollectorNotificationReceiver<ConsensusNotification>) -> Self {
+    pub fn new(
+        utxoindex: Option<UtxoIndexProxy>,
+        txindex: Option<TxIndexProxy>,
+        addresshistory: Option<AddressHistoryProxy>,
+        recv_channel: CollectorNotificationReceiver<ConsensusNotification>,
+    ) -> Self {
         Self {
             utxoindex,
+            txindex,
+            addresshistory,
             recv_channel,
             collect_shutdown: Arc::new(SingleTrigger::new()),
             is_started: Arc::new(AtomicBool::new(false)),
@@ -58,12 +75,13 @@ impl Processor {
 
             while let Ok(notification) = self.recv_channel.recv().await {
                 match self.process_notification(notification).await {
-                    Ok(notification) => match notifier.notify(notification) {
+                    Ok(Some(notification)) => match notifier.notify(notification) {
                         Ok(_) => (),
                         Err(err) => {
                             trace!("[Index processor] notification sender error: {err:?}");
                         }
                     },
+                    Ok(None) => (),
                     Err(err) => {
                         trace!("[Index processor] error while processing a consensus notification: {err:?}");
                     }
@@ -76,13 +94,18 @@ impl Processor {
         });
     }
 
-    async fn process_notification(self: &Arc<Self>, notification: ConsensusNotification) -> IndexResult<Notification> {
+    /// Processes a consensus notification, returning the index notification to relay, if any.
+    async fn process_notification(self: &Arc<Self>, notification: ConsensusNotification) -> IndexResult<Option<Notification>> {
         match notification {
             ConsensusNotification::UtxosChanged(utxos_changed) => {
-                Ok(Notification::UtxosChanged(self.process_utxos_changed(utxos_changed).await?))
+                Ok(self.process_utxos_changed(utxos_changed).await?.map(Notification::UtxosChanged))
             }
             ConsensusNotification::PruningPointUtxoSetOverride(_) => {
-                Ok(Notification::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification {}))
+                Ok(Some(Notification::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification {})))
+            }
+            ConsensusNotification::VirtualChainChanged(virtual_chain_changed) => {
+                self.process_virtual_chain_changed(virtual_chain_changed).await?;
+                Ok(None)
             }
             _ => Err(IndexError::NotSupported(notification.event_type())),
         }
@@ -91,8 +114,15 @@ impl Processor {
     async fn process_utxos_changed(
         self: &Arc<Self>,
         notification: consensus_notification::UtxosChangedNotification,
-    ) -> IndexResult<UtxosChangedNotification> {
+    ) -> IndexResult<Option<UtxosChangedNotification>> {
         trace!("[{IDENT}]: processing {:?}", notification);
+        if self.utxoindex.is_none() && self.addresshistory.is_none() {
+            return Err(IndexError::NotSupported(EventType::UtxosChanged));
+        }
+        if let Some(addresshistory) = self.addresshistory.clone() {
+            // Consensus notifies of the UTXO changes before the matching virtual chain changes
+            addresshistory.record_utxo_diff(notification.accumulated_utxo_diff.clone()).await;
+        }
         if let Some(utxoindex) = self.utxoindex.clone() {
             let converted_notification: UtxosChangedNotification =
                 utxoindex.update(notification.accumulated_utxo_diff.clone(), notification.virtual_parents).await?.into();
@@ -101,9 +131,38 @@ impl Processor {
                 converted_notification.added.len(),
                 converted_notification.removed.len()
             );
-            return Ok(converted_notification);
+            return Ok(Some(converted_notification));
         };
-        Err(IndexError::NotSupported(EventType::UtxosChanged))
+        Ok(None)
+    }
+
+    async fn process_virtual_chain_changed(
+        self: &Arc<Self>,
+        notification: consensus_notification::VirtualChainChangedNotification,
+    ) -> IndexResult<()> {
+        trace!("[{IDENT}]: processing {:?}", notification);
+        if self.txindex.is_none() && self.addresshistory.is_none() {
+            return Err(IndexError::NotSupported(EventType::VirtualChainChanged));
+        }
+        if let Some(txindex) = self.txindex.clone() {
+            txindex
+                .update(
+                    notification.added_chain_block_hashes.clone(),
+                    notification.removed_chain_block_hashes.clone(),
+                    notification.added_chain_blocks_acceptance_data.clone(),
+                )
+                .await?;
+        }
+        if let Some(addresshistory) = self.addresshistory.clone() {
+            addresshistory
+                .update(
+                    notification.added_chain_block_hashes,
+                    notification.removed_chain_block_hashes,
+                    notification.added_chain_blocks_acceptance_data,
+                )
+                .await?;
+        }
+        Ok(())
     }
 
     async fn join_collecting_task(&self) -> Result<()> {
@@ -129,14 +188,22 @@ impl Collector<Notification> for Processor {
 mod tests {
     use super::*;
     use async_channel::{unbounded, Receiver, Sender};
-    use karlsen_consensus::{config::Config, consensus::test_consensus::TestConsensus, params::DEVNET_PARAMS, test_helpers::*};
-    use karlsen_consensus_core::utxo::{utxo_collection::UtxoCollection, utxo_diff::UtxoDiff};
+    use karlsen_consensus::{config::ConfigBuilder, consensus::test_consensus::TestConsensus, params::DEVNET_PARAMS, test_helpers::*};
+    use karlsen_consensus_core::{
+        api::ConsensusApi,
+        coinbase::MinerData,
+        tx::{ScriptPublicKey, TransactionId},
+        utxo::{utxo_collection::UtxoCollection, utxo_diff::UtxoDiff},
+    };
     use karlsen_consensusmanager::ConsensusManager;
     use karlsen_database::create_temp_db;
     use karlsen_database::prelude::ConnBuilder;
     use karlsen_database::utils::DbLifetime;
+    use karlsen_hashes::Hash;
     use karlsen_notify::notifier::test_helpers::NotifyMock;
+    use karlsen_txindex::{api::TxIndexApi, TxIndex};
     use karlsen_utxoindex::UtxoIndex;
+    use parking_lot::RwLock;
     use rand::{rngs::SmallRng, SeedableRng};
     use std::sync::Arc;
 
@@ -147,24 +214,96 @@ mod tests {
         consensus_sender: Sender<ConsensusNotification>,
         processor: Arc<Processor>,
         processor_receiver: Receiver<Notification>,
+        txindex: Arc<RwLock<TxIndex>>,
         test_consensus: TestConsensus,
         utxoindex_db_lifetime: DbLifetime,
+        txindex_db_lifetime: DbLifetime,
     }
 
     impl NotifyPipeline {
         fn new() -> Self {
             let (consensus_sender, consensus_receiver) = unbounded();
             let (utxoindex_db_lifetime, utxoindex_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
-            let config = Arc::new(Config::new(DEVNET_PARAMS));
+            let (txindex_db_lifetime, txindex_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Arc::new(ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build());
             let tc = TestConsensus::new(&config);
             tc.init();
             let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
-            let utxoindex = Some(UtxoIndexProxy::new(UtxoIndex::new(consensus_manager, utxoindex_db).unwrap()));
-            let processor = Arc::new(Processor::new(utxoindex, consensus_receiver));
+            let utxoindex = Some(UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap()));
+            let txindex = TxIndex::new(consensus_manager, txindex_db).unwrap();
+            let processor = Arc::new(Processor::new(utxoindex, Some(TxIndexProxy::new(txindex.clone())), None, consensus_receiver));
             let (processor_sender, processor_receiver) = unbounded();
             let notifier = Arc::new(NotifyMock::new(processor_sender));
             processor.clone().start(notifier);
-            Self { test_consensus: tc, consensus_sender, processor, processor_receiver, utxoindex_db_lifetime }
+            Self {
+                test_consensus: tc,
+                consensus_sender,
+                processor,
+                processor_receiver,
+                txindex,
+                utxoindex_db_lifetime,
+                txindex_db_lifetime,
+            }
+        }
+
+        /// Adds a chain of blocks on top of `parent` and returns their hashes.
+        ///
+        /// `extra_data` goes into the coinbase payloads, so chains mined with distinct extra data accept distinct coinbase transactions.
+        async fn add_chain(&self, parent: Hash, hashes: impl Iterator<Item = u64>, extra_data: &[u8]) -> Vec<Hash> {
+            let mut chain = vec![];
+            for hash in hashes.map(Hash::from) {
+                let miner_data = MinerData::new(ScriptPublicKey::from_vec(0, vec![]), extra_data.to_vec());
+                let block = self.test_consensus.build_utxo_valid_block_with_parents(
+                    hash,
+                    vec![chain.last().copied().unwrap_or(parent)],
+                    miner_data,
+                    vec![],
+                );
+                self.test_consensus.validate_and_insert_block(block.to_immutable()).virtual_state_task.await.unwrap();
+                chain.push(hash);
+            }
+            chain
+        }
+
+        fn virtual_chain_changed(&self, added: Vec<Hash>, removed: Vec<Hash>) -> ConsensusNotification {
+            let acceptance_data = added.iter().map(|hash| self.test_consensus.get_block_acceptance_data(*hash).unwrap()).collect();
+            ConsensusNotification::VirtualChainChanged(consensus_notification::VirtualChainChangedNotification::new(
+                Arc::new(added),
+                Arc::new(removed),
+                Arc::new(acceptance_data),
+            ))
+        }
+
+        /// Waits for all previously sent notifications to be processed.
+        ///
+        /// Notifications are processed in order, so relaying a PruningPointUtxoSetOverride acts as a barrier.
+        async fn flush(&self) {
+            self.consensus_sender
+                .send(ConsensusNotification::PruningPointUtxoSetOverride(
+                    consensus_notification::PruningPointUtxoSetOverrideNotification {},
+                ))
+                .await
+                .expect("expected send");
+            match self.processor_receiver.recv().await.expect("expected recv") {
+                Notification::PruningPointUtxoSetOverride(_) => (),
+                unexpected_notification => panic!("Unexpected notification: {unexpected_notification:?}"),
+            }
+        }
+
+        /// Returns the ids of the transactions accepted by `chain` along with their accepting block
+        fn accepted_transactions(&self, chain: &[Hash]) -> Vec<(TransactionId, Hash)> {
+            chain
+                .iter()
+                .flat_map(|hash| {
+                    self.test_consensus
+                        .get_block_acceptance_data(*hash)
+                        .unwrap()
+                        .iter()
+                        .flat_map(|mergeset_block_data| mergeset_block_data.accepted_transactions.iter().map(|tx| tx.transaction_id))
+                        .map(|transaction_id| (transaction_id, *hash))
+                        .collect::<Vec<_>>()
+                })
+                .collect()
         }
     }
 
@@ -251,4 +390,61 @@ mod tests {
         pipeline.consensus_sender.close();
         pipeline.processor.clone().join().await.expect("stopping the processor must succeed");
     }
+
+    #[tokio::test]
+    async fn test_virtual_chain_changed_notification() {
+        let pipeline = NotifyPipeline::new();
+        let genesis = pipeline.test_consensus.get_sink();
+
+        // Extend the selected chain and notify of the added chain blocks
+        let chain = pipeline.add_chain(genesis, 1..=4, b"chain").await;
+        pipeline.consensus_sender.send(pipeline.virtual_chain_changed(chain.clone(), vec![])).await.expect("expected send");
+        pipeline.flush().await;
+
+        let accepted = pipeline.accepted_transactions(&chain);
+        assert!(!accepted.is_empty());
+        for (transaction_id, accepting_block_hash) in accepted.iter().copied() {
+            let entry =
+                pipeline.txindex.read().get_transaction_entry(transaction_id).unwrap().expect("expected an indexed transaction");
+            assert_eq!(entry.accepting_block_hash, accepting_block_hash);
+            assert_eq!(entry.accepting_block_daa_score, pipeline.test_consensus.get_header(accepting_block_hash).unwrap().daa_score);
+        }
+        assert_eq!(pipeline.txindex.read().get_txindex_sink().unwrap(), *chain.last().unwrap());
+
+        // Reorg to a longer chain forking from genesis and notify of the removed and added chain blocks
+        let fork = pipeline.add_chain(genesis, 11..=16, b"fork").await;
+        assert_eq!(pipeline.test_consensus.get_sink(), *fork.last().unwrap());
+        let removed = chain.iter().rev().copied().collect();
+        pipeline.consensus_sender.send(pipeline.virtual_chain_changed(fork.clone(), removed)).await.expect("expected send");
+        pipeline.flush().await;
+
+        let fork_accepted = pipeline.accepted_transactions(&fork);
+        for (transaction_id, accepting_block_hash) in fork_accepted.iter().copied() {
+            let entry =
+                pipeline.txindex.read().get_transaction_entry(transaction_id).unwrap().expect("expected an indexed transaction");
+            assert_eq!(entry.accepting_block_hash, accepting_block_hash);
+        }
+
+        // Transactions accepted by the removed chain blocks are either unindexed or reassigned to a fork block
+        let (mut unindexed, mut reassigned) = (0, 0);
+        for (transaction_id, _) in accepted.iter().copied() {
+            match pipeline.txindex.read().get_transaction_entry(transaction_id).unwrap() {
+                Some(entry) => {
+                    assert!(fork.contains(&entry.accepting_block_hash));
+                    reassigned += 1;
+                }
+                None => {
+                    assert!(fork_accepted.iter().all(|(id, _)| *id != transaction_id));
+                    unindexed += 1;
+                }
+            }
+        }
+        assert!(unindexed > 0);
+        assert_eq!(reassigned, accepted.iter().filter(|(id, _)| fork_accepted.iter().any(|(fork_id, _)| fork_id == id)).count());
+        assert_eq!(pipeline.txindex.read().get_txindex_sink().unwrap(), *fork.last().unwrap());
+
+        assert!(pipeline.processor_receiver.is_empty(), "the notification receiver should be empty");
+        pipeline.consensus_sender.close();
+        pipeline.processor.clone().join().await.expect("stopping the processor must succeed");
+    }
 }
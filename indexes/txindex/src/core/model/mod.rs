@@ -0,0 +1,25 @@
+use karlsen_hashes::Hash;
+use karlsen_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+
+/// The location of an accepted transaction within the DAG, as recorded by the [`TxIndex`](crate::TxIndex).
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TxIndexEntry {
+    /// The block containing the transaction
+    pub block_hash: Hash,
+    /// The position of the transaction within the containing block
+    pub index_within_block: u32,
+    /// The chain block accepting the transaction
+    pub accepting_block_hash: Hash,
+    /// The DAA score of the accepting chain block
+    pub accepting_block_daa_score: u64,
+}
+
+impl TxIndexEntry {
+    /// Creates a new [`TxIndexEntry`]
+    pub fn new(block_hash: Hash, index_within_block: u32, accepting_block_hash: Hash, accepting_block_daa_score: u64) -> Self {
+        Self { block_hash, index_within_block, accepting_block_hash, accepting_block_daa_score }
+    }
+}
+
+impl MemSizeEstimator for TxIndexEntry {}
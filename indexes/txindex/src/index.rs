@@ -0,0 +1,324 @@
+use crate::{
+    api::TxIndexApi,
+    errors::{TxIndexError, TxIndexResult},
+    model::TxIndexEntry,
+    stores::store_manager::Store,
+    IDENT,
+};
+use karlsen_consensus_core::{
+    acceptance_data::AcceptanceData, api::ConsensusApi, errors::consensus::ConsensusResult, tx::TransactionId,
+};
+use karlsen_consensusmanager::{ConsensusManager, ConsensusResetHandler};
+use karlsen_core::{info, trace, warn};
+use karlsen_database::prelude::{StoreError, StoreResult, DB};
+use karlsen_hashes::Hash;
+use parking_lot::RwLock;
+use std::{
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+
+const RESYNC_CHUNK_SIZE: usize = 1024;
+
+/// TxIndex maps the ids of transactions accepted by the selected chain to a [`TxIndexEntry`] locating them in the DAG,
+/// and commits them to its own store.
+///
+/// Entries are never dropped when consensus prunes the data of the blocks they point to, so the index keeps answering
+/// queries about transactions below the pruning point.
+/// Note: The TxIndex struct by itself is not thread save, only correct usage of the supplied RwLock via `new` makes it so.
+/// please follow guidelines found in the comments under `txindex::core::api::TxIndexApi` for proper thread safety.
+pub struct TxIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+}
+
+impl TxIndex {
+    /// Creates a new [`TxIndex`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> TxIndexResult<Arc<RwLock<Self>>> {
+        let mut txindex = Self { consensus_manager: consensus_manager.clone(), store: Store::new(db) };
+        if !txindex.is_synced()? {
+            txindex.resync()?;
+        }
+        let txindex = Arc::new(RwLock::new(txindex));
+        consensus_manager.register_consensus_reset_handler(Arc::new(TxIndexConsensusResetHandler::new(Arc::downgrade(&txindex))));
+        Ok(txindex)
+    }
+
+    /// Applies a selected chain change to the store:
+    /// 1) removes the entries of transactions accepted by the removed chain blocks.
+    /// 2) adds entries for all transactions accepted by the added chain blocks.
+    /// 3) commits the last added chain block as the txindex sink.
+    fn apply_chain_changes(
+        &mut self,
+        consensus: &dyn ConsensusApi,
+        added_chain_block_hashes: &[Hash],
+        removed_chain_block_hashes: &[Hash],
+        added_chain_blocks_acceptance_data: &[Arc<AcceptanceData>],
+    ) -> TxIndexResult<()> {
+        let mut to_remove: Vec<TransactionId> = Vec::new();
+        for removed_block in removed_chain_block_hashes.iter().copied() {
+            let acceptance_data = match consensus.get_block_acceptance_data(removed_block) {
+                Ok(acceptance_data) => acceptance_data,
+                Err(err) => {
+                    warn!("[{0}] cannot unindex the transactions accepted by removed chain block {1}: {2}", IDENT, removed_block, err);
+                    continue;
+                }
+            };
+            for transaction_id in acceptance_data.iter().flat_map(|x| x.accepted_transactions.iter().map(|tx| tx.transaction_id)) {
+                // Only remove entries which are still attributed to the removed block
+                if let Some(entry) = self.store.get_transaction_entry(transaction_id)? {
+                    if entry.accepting_block_hash == removed_block {
+                        to_remove.push(transaction_id);
+                    }
+                }
+            }
+        }
+
+        let mut to_add: Vec<(TransactionId, TxIndexEntry)> = Vec::new();
+        for (accepting_block_hash, acceptance_data) in added_chain_block_hashes.iter().copied().zip(added_chain_blocks_acceptance_data)
+        {
+            let accepting_block_daa_score = consensus.get_header(accepting_block_hash)?.daa_score;
+            for mergeset_block_data in acceptance_data.iter() {
+                to_add.extend(mergeset_block_data.accepted_transactions.iter().map(|tx| {
+                    (
+                        tx.transaction_id,
+                        TxIndexEntry::new(
+                            mergeset_block_data.block_hash,
+                            tx.index_within_block,
+                            accepting_block_hash,
+                            accepting_block_daa_score,
+                        ),
+                    )
+                }));
+            }
+        }
+
+        trace!("[{0}] adding {1} transactions", IDENT, to_add.len());
+        trace!("[{0}] removing {1} transactions", IDENT, to_remove.len());
+        self.store.update_transaction_entries(&to_add, &to_remove)?;
+
+        if let Some(sink) = added_chain_block_hashes.last().copied() {
+            self.store.set_sink(sink)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TxIndexApi for TxIndex {
+    fn get_transaction_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        trace!("[{0}] retrieving entry of transaction {1}", IDENT, transaction_id);
+
+        self.store.get_transaction_entry(transaction_id)
+    }
+
+    fn get_transaction_entries(&self, transaction_ids: Vec<TransactionId>) -> StoreResult<Vec<Option<TxIndexEntry>>> {
+        trace!("[{0}] retrieving entries of {1} transactions", IDENT, transaction_ids.len());
+
+        transaction_ids.into_iter().map(|transaction_id| self.store.get_transaction_entry(transaction_id)).collect()
+    }
+
+    fn get_txindex_sink(&self) -> StoreResult<Hash> {
+        trace!("[{0}] retrieving sink", IDENT);
+
+        self.store.get_sink()
+    }
+
+    /// Checks to see if the [TxIndex] is sync'd. This is done via comparing the txindex committed sink with the one of the consensus database.
+    ///
+    /// **Note:** Due to sync gaps between the txindex and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_synced(&self) -> TxIndexResult<bool> {
+        trace!("[{0}] checking sync status...", IDENT);
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        match self.store.get_sink() {
+            Ok(txindex_sink) => {
+                let res = txindex_sink == session.get_sink();
+                trace!("[{0}] sync status is {1}", IDENT, res);
+                Ok(res)
+            }
+            Err(StoreError::KeyNotFound(_)) => {
+                // Means the txindex sink is empty i.e. not sync'd.
+                trace!("[{0}] sync status is {1}", IDENT, false);
+                Ok(false)
+            }
+            Err(other_store_errors) => Err(TxIndexError::StoreAccessError(other_store_errors)),
+        }
+    }
+
+    /// Updates the [TxIndex] via the virtual chain changes supplied.
+    fn update(
+        &mut self,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()> {
+        trace!("[{0}] updating...", IDENT);
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+        self.apply_chain_changes(
+            &*session,
+            &added_chain_block_hashes,
+            &removed_chain_block_hashes,
+            &added_chain_blocks_acceptance_data,
+        )
+    }
+
+    /// Syncs the [TxIndex] with the selected chain of the consensus database.
+    ///
+    /// Walks the chain from the last committed sink if consensus still knows it, otherwise from the retention period root.
+    /// Existing entries are kept in both cases so that the index outlives consensus pruning and resets.
+    ///
+    /// **Note:** resyncing while consensus notifies of virtual chain changes, may result in a corrupted db.
+    fn resync(&mut self) -> TxIndexResult<()> {
+        info!("Resyncing the txindex...");
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let mut low = match self.store.get_sink() {
+            Ok(sink) if session.get_virtual_chain_from_block(sink, Some(0)).is_ok() => sink,
+            Ok(_) | Err(StoreError::KeyNotFound(_)) => {
+                // Either the txindex is empty or its sink is unknown to the current consensus (e.g. following a consensus reset),
+                // so we start over from the earliest block having full data. The acceptance data of that block is indexed as
+                // well since the chain path excludes its starting point.
+                let retention_period_root = session.get_retention_period_root();
+                if let Ok(acceptance_data) = session.get_block_acceptance_data(retention_period_root) {
+                    self.apply_chain_changes(&*session, &[retention_period_root], &[], &[acceptance_data])?;
+                }
+                self.store.set_sink(retention_period_root)?;
+                retention_period_root
+            }
+            Err(other_store_errors) => return Err(TxIndexError::StoreAccessError(other_store_errors)),
+        };
+
+        loop {
+            let chain_path = session.get_virtual_chain_from_block(low, Some(RESYNC_CHUNK_SIZE))?;
+            trace!("[{0}] resyncing with a batch of {1} chain blocks from consensus db", IDENT, chain_path.added.len());
+            let acceptance_data =
+                chain_path.added.iter().map(|hash| session.get_block_acceptance_data(*hash)).collect::<ConsensusResult<Vec<_>>>()?;
+            self.apply_chain_changes(&*session, &chain_path.added, &chain_path.removed, &acceptance_data)?;
+
+            match chain_path.added.last() {
+                Some(last) if chain_path.added.len() == RESYNC_CHUNK_SIZE => low = *last,
+                _ => break,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for TxIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxIndex").finish()
+    }
+}
+
+struct TxIndexConsensusResetHandler {
+    txindex: Weak<RwLock<TxIndex>>,
+}
+
+impl TxIndexConsensusResetHandler {
+    fn new(txindex: Weak<RwLock<TxIndex>>) -> Self {
+        Self { txindex }
+    }
+}
+
+impl ConsensusResetHandler for TxIndexConsensusResetHandler {
+    fn handle_consensus_reset(&self) {
+        if let Some(txindex) = self.txindex.upgrade() {
+            txindex.write().resync().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{api::TxIndexApi, TxIndex};
+    use karlsen_consensus::{config::ConfigBuilder, consensus::test_consensus::TestConsensus, params::DEVNET_PARAMS};
+    use karlsen_consensus_core::api::ConsensusApi;
+    use karlsen_consensusmanager::ConsensusManager;
+    use karlsen_database::create_temp_db;
+    use karlsen_database::prelude::ConnBuilder;
+    use karlsen_hashes::Hash;
+    use std::sync::Arc;
+
+    /// Asserts that all transactions accepted by `chain` are indexed and returns their count
+    fn assert_chain_is_indexed(tc: &TestConsensus, txindex: &TxIndex, chain: &[Hash]) -> usize {
+        let mut count = 0;
+        for accepting_block_hash in chain.iter().copied() {
+            let daa_score = tc.get_header(accepting_block_hash).unwrap().daa_score;
+            for mergeset_block_data in tc.get_block_acceptance_data(accepting_block_hash).unwrap().iter() {
+                for tx in mergeset_block_data.accepted_transactions.iter() {
+                    let entry = txindex.get_transaction_entry(tx.transaction_id).unwrap().expect("expected an indexed transaction");
+                    assert_eq!(entry.block_hash, mergeset_block_data.block_hash);
+                    assert_eq!(entry.index_within_block, tx.index_within_block);
+                    assert_eq!(entry.accepting_block_hash, accepting_block_hash);
+                    assert_eq!(entry.accepting_block_daa_score, daa_score);
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    #[tokio::test]
+    async fn test_txindex() {
+        karlsen_core::log::try_init_logger("INFO");
+
+        let (_txindex_db_lifetime, txindex_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let config = ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build();
+        let tc = Arc::new(TestConsensus::new(&config));
+        let wait_handles = tc.init();
+
+        // Build an initial selected chain
+        let mut chain = vec![config.genesis.hash];
+        for i in 1..=5u64 {
+            let hash: Hash = i.into();
+            tc.add_utxo_valid_block_with_parents(hash, vec![*chain.last().unwrap()], vec![]).await.unwrap();
+            chain.push(hash);
+        }
+
+        // Sync txindex from scratch.
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+        let txindex = TxIndex::new(consensus_manager, txindex_db).unwrap();
+        assert!(txindex.read().is_synced().unwrap());
+        assert_eq!(txindex.read().get_txindex_sink().unwrap(), tc.get_sink());
+        assert!(assert_chain_is_indexed(&tc, &txindex.read(), &chain[1..]) > 0);
+
+        // Reorg to a longer chain forking from genesis and resync from the previous sink.
+        let mut fork = vec![config.genesis.hash];
+        for i in 11..=18u64 {
+            let hash: Hash = i.into();
+            tc.add_utxo_valid_block_with_parents(hash, vec![*fork.last().unwrap()], vec![]).await.unwrap();
+            fork.push(hash);
+        }
+        assert_eq!(tc.get_sink(), *fork.last().unwrap());
+        assert!(!txindex.read().is_synced().unwrap());
+        txindex.write().resync().unwrap();
+        assert!(txindex.read().is_synced().unwrap());
+        assert!(assert_chain_is_indexed(&tc, &txindex.read(), &fork[1..]) > 0);
+
+        // Entries accepted by the reorged chain must be gone or re-attributed to the new chain
+        let mut reorged_count = 0;
+        for accepting_block_hash in chain[1..].iter().copied() {
+            for mergeset_block_data in tc.get_block_acceptance_data(accepting_block_hash).unwrap().iter() {
+                for tx in mergeset_block_data.accepted_transactions.iter() {
+                    if let Some(entry) = txindex.read().get_transaction_entry(tx.transaction_id).unwrap() {
+                        assert!(fork.contains(&entry.accepting_block_hash));
+                    }
+                    reorged_count += 1;
+                }
+            }
+        }
+        assert!(reorged_count > 0);
+
+        // Deconstruct
+        drop(txindex);
+        tc.shutdown(wait_handles);
+    }
+}
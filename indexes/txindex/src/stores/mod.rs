@@ -0,0 +1,3 @@
+mod sink;
+pub mod store_manager;
+mod transactions;
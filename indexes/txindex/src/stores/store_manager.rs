@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use karlsen_consensus_core::tx::TransactionId;
+use karlsen_database::prelude::{CachePolicy, StoreResult, DB};
+use karlsen_hashes::Hash;
+
+use crate::{
+    model::TxIndexEntry,
+    stores::{
+        sink::{DbTxIndexSinkStore, TxIndexSinkStore, TxIndexSinkStoreReader},
+        transactions::{DbTxIndexEntryStore, TxIndexEntryStore, TxIndexEntryStoreReader},
+    },
+};
+
+#[derive(Clone)]
+pub struct Store {
+    txindex_sink_store: DbTxIndexSinkStore,
+    tx_entries_store: DbTxIndexEntryStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            txindex_sink_store: DbTxIndexSinkStore::new(db.clone()),
+            tx_entries_store: DbTxIndexEntryStore::new(db, CachePolicy::Count(10_000)),
+        }
+    }
+
+    pub fn get_transaction_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        self.tx_entries_store.get(transaction_id)
+    }
+
+    pub fn update_transaction_entries(
+        &mut self,
+        to_add: &[(TransactionId, TxIndexEntry)],
+        to_remove: &[TransactionId],
+    ) -> StoreResult<()> {
+        // A transaction can be both removed (by a reorged chain block) and re-added (by a new chain block accepting it),
+        // thus we must first apply removals and then additions.
+        self.tx_entries_store.remove_entries(to_remove)?;
+        self.tx_entries_store.add_entries(to_add)
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.txindex_sink_store.get()
+    }
+
+    pub fn set_sink(&mut self, sink: Hash) -> StoreResult<()> {
+        self.txindex_sink_store.set(sink)
+    }
+}
@@ -0,0 +1,61 @@
+use crate::core::model::TxIndexEntry;
+
+use karlsen_consensus_core::tx::TransactionId;
+use karlsen_database::prelude::{CachePolicy, CachedDbAccess, DirectDbWriter, StoreError, StoreResult, DB};
+use karlsen_database::registry::DatabaseStorePrefixes;
+use std::sync::Arc;
+
+// Traits:
+
+pub trait TxIndexEntryStoreReader {
+    /// Get the [TxIndexEntry] of a transaction, if indexed.
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>>;
+}
+
+pub trait TxIndexEntryStore: TxIndexEntryStoreReader {
+    /// add [TxIndexEntry]s into the [TxIndexEntryStore], overriding previous entries of the same transactions.
+    fn add_entries(&mut self, entries: &[(TransactionId, TxIndexEntry)]) -> StoreResult<()>;
+
+    /// remove the [TxIndexEntry]s of the given transactions from the [TxIndexEntryStore].
+    fn remove_entries(&mut self, transaction_ids: &[TransactionId]) -> StoreResult<()>;
+}
+
+// Implementations:
+
+#[derive(Clone)]
+pub struct DbTxIndexEntryStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<TransactionId, TxIndexEntry>,
+}
+
+impl DbTxIndexEntryStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndex.into()) }
+    }
+}
+
+impl TxIndexEntryStoreReader for DbTxIndexEntryStore {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        match self.access.read(transaction_id) {
+            Ok(entry) => Ok(Some(entry)),
+            Err(StoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl TxIndexEntryStore for DbTxIndexEntryStore {
+    fn add_entries(&mut self, entries: &[(TransactionId, TxIndexEntry)]) -> StoreResult<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        self.access.write_many(DirectDbWriter::new(&self.db), &mut entries.iter().copied())
+    }
+
+    fn remove_entries(&mut self, transaction_ids: &[TransactionId]) -> StoreResult<()> {
+        if transaction_ids.is_empty() {
+            return Ok(());
+        }
+        self.access.delete_many(DirectDbWriter::new(&self.db), &mut transaction_ids.iter().copied())
+    }
+}
@@ -8,7 +8,12 @@ use karlsen_database::prelude::StoreResult;
 use karlsen_hashes::Hash;
 use karlsen_index_core::indexed_utxos::BalanceByScriptPublicKey;
 use parking_lot::RwLock;
-use std::{collections::HashSet, fmt::Debug, sync::Arc};
+use std::{
+    collections::HashSet,
+    fmt::Debug,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use crate::{
     errors::UtxoIndexResult,
@@ -44,6 +49,11 @@ pub trait UtxoIndexApi: Send + Sync + Debug {
     /// 2) due to potential sync-gaps is_synced is unreliable while consensus is actively resolving virtual states.  
     fn is_synced(&self) -> UtxoIndexResult<bool>;
 
+    /// Creates a consistent checkpoint of the utxoindex db at `path`.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn create_checkpoint(&self, path: &Path) -> StoreResult<()>;
+
     /// Update the utxoindex with the given utxo_diff, and tips.
     ///
     /// Note: Use a write lock when accessing this method
@@ -81,6 +91,10 @@ impl UtxoIndexProxy {
         spawn_blocking(move || self.inner.read().get_balance_by_script_public_keys(script_public_keys)).await.unwrap()
     }
 
+    pub async fn create_checkpoint(self, path: PathBuf) -> StoreResult<()> {
+        spawn_blocking(move || self.inner.read().create_checkpoint(&path)).await.unwrap()
+    }
+
     pub async fn update(self, utxo_diff: Arc<UtxoDiff>, tips: Arc<Vec<Hash>>) -> UtxoIndexResult<UtxoChanges> {
         spawn_blocking(move || self.inner.write().update(utxo_diff, tips)).await.unwrap()
     }
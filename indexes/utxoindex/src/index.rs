@@ -16,6 +16,7 @@ use karlsen_utils::arc::ArcExtensions;
 use parking_lot::RwLock;
 use std::{
     fmt::Debug,
+    path::Path,
     sync::{Arc, Weak},
 };
 
@@ -136,6 +137,12 @@ impl UtxoIndexApi for UtxoIndex {
             },
         }
     }
+
+    /// Creates a consistent checkpoint of the utxoindex db at `path`.
+    fn create_checkpoint(&self, path: &Path) -> StoreResult<()> {
+        self.store.create_checkpoint(path)
+    }
+
     /// Deletes and reinstates the utxoindex database, syncing it from scratch via the consensus database.
     ///
     /// **Notes:**
@@ -1,4 +1,4 @@
-use std::{collections::HashSet, sync::Arc};
+use std::{collections::HashSet, path::Path, sync::Arc};
 
 use karlsen_consensus_core::{
     tx::{ScriptPublicKeys, TransactionOutpoint},
@@ -20,6 +20,7 @@ use crate::{
 
 #[derive(Clone)]
 pub struct Store {
+    db: Arc<DB>,
     utxoindex_tips_store: DbUtxoIndexTipsStore,
     circulating_supply_store: DbCirculatingSupplyStore,
     utxos_by_script_public_key_store: DbUtxoSetByScriptPublicKeyStore,
@@ -28,6 +29,7 @@ pub struct Store {
 impl Store {
     pub fn new(db: Arc<DB>) -> Self {
         Self {
+            db: db.clone(),
             utxoindex_tips_store: DbUtxoIndexTipsStore::new(db.clone()),
             circulating_supply_store: DbCirculatingSupplyStore::new(db.clone()),
             utxos_by_script_public_key_store: DbUtxoSetByScriptPublicKeyStore::new(db, CachePolicy::Empty),
@@ -42,6 +44,12 @@ impl Store {
         self.utxos_by_script_public_key_store.get_balance_from_script_public_keys(script_public_keys)
     }
 
+    /// Creates a checkpoint of the utxoindex db at `path`.
+    pub fn create_checkpoint(&self, path: &Path) -> StoreResult<()> {
+        trace!("[{0}] creating a db checkpoint at {1}", IDENT, path.display());
+        self.db.create_checkpoint(path)
+    }
+
     // This can have a big memory footprint, so it should be used only for tests.
     pub fn get_all_outpoints(&self) -> StoreResult<HashSet<TransactionOutpoint>> {
         self.utxos_by_script_public_key_store.get_all_outpoints()
@@ -1,3 +1,4 @@
+use crate::snapshot::SnapshotCommand;
 use clap::{arg, Arg, ArgAction, Command};
 use karlsen_consensus_core::{
     config::Config,
@@ -5,11 +6,15 @@ use karlsen_consensus_core::{
 };
 use karlsen_core::karlsend_env::version;
 use karlsen_notify::address::tracker::Tracker;
+use karlsen_rpc_core::{
+    api::{auth::RpcAccessPolicy, rate_limit::RpcRateLimit},
+    RpcResult,
+};
 use karlsen_utils::networking::ContextualNetAddress;
 use karlsen_wrpc_server::address::WrpcNetAddress;
 use serde::Deserialize;
 use serde_with::{serde_as, DisplayFromStr};
-use std::{ffi::OsString, fs};
+use std::{ffi::OsString, fs, path::PathBuf};
 use toml::from_str;
 
 #[cfg(feature = "devnet-prealloc")]
@@ -28,6 +33,7 @@ pub struct Args {
     // NOTE: it is best if property names match config file fields
     pub appdir: Option<String>,
     pub logdir: Option<String>,
+    pub backupdir: Option<String>,
     #[serde(rename = "nologfiles")]
     pub no_log_files: bool,
     #[serde_as(as = "Option<DisplayFromStr>")]
@@ -38,6 +44,13 @@ pub struct Args {
     pub rpclisten_json: Option<WrpcNetAddress>,
     #[serde(rename = "unsaferpc")]
     pub unsafe_rpc: bool,
+    #[serde(rename = "rpc-token")]
+    pub rpc_tokens: Vec<String>,
+    #[serde(rename = "rpc-role")]
+    pub rpc_roles: Vec<String>,
+    pub rpc_anonymous_role: Option<String>,
+    pub rpc_rate_limit: u32,
+    pub rpc_rate_burst: u32,
     pub wrpc_verbose: bool,
     #[serde(rename = "loglevel")]
     pub log_level: String,
@@ -53,6 +66,8 @@ pub struct Args {
     #[serde(rename = "uacomment")]
     pub user_agent_comments: Vec<String>,
     pub utxoindex: bool,
+    pub txindex: bool,
+    pub addresshistoryindex: bool,
     pub reset_db: bool,
     #[serde(rename = "outpeers")]
     pub outbound_target: usize,
@@ -75,6 +90,12 @@ pub struct Args {
     pub externalip: Option<ContextualNetAddress>,
     pub perf_metrics: bool,
     pub perf_metrics_interval_sec: u64,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub prometheus_listen: Option<ContextualNetAddress>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub stratum_listen: Option<ContextualNetAddress>,
+    pub stratum_pay_address: Option<String>,
+    pub stratum_difficulty: u64,
     pub block_template_cache_lifetime: Option<u64>,
 
     #[cfg(feature = "devnet-prealloc")]
@@ -93,6 +114,9 @@ pub struct Args {
     pub retention_period_days: Option<f64>,
     #[serde(rename = "full-dataset")]
     pub full_dataset: bool,
+    /// Snapshot subcommand to run instead of starting the node
+    #[serde(skip)]
+    pub snapshot: Option<SnapshotCommand>,
 }
 
 impl Default for Args {
@@ -103,8 +127,15 @@ impl Default for Args {
             rpclisten_borsh: None,
             rpclisten_json: None,
             unsafe_rpc: false,
+            rpc_tokens: vec![],
+            rpc_roles: vec![],
+            rpc_anonymous_role: None,
+            rpc_rate_limit: 0,
+            rpc_rate_burst: 1000,
             async_threads: num_cpus::get(),
             utxoindex: false,
+            txindex: false,
+            addresshistoryindex: false,
             reset_db: false,
             outbound_target: 8,
             inbound_limit: 128,
@@ -119,6 +150,7 @@ impl Default for Args {
             archival: false,
             sanity: false,
             logdir: None,
+            backupdir: None,
             rpclisten: None,
             wrpc_verbose: false,
             log_level: "INFO".into(),
@@ -129,6 +161,10 @@ impl Default for Args {
             yes: false,
             perf_metrics: false,
             perf_metrics_interval_sec: 10,
+            prometheus_listen: None,
+            stratum_listen: None,
+            stratum_pay_address: None,
+            stratum_difficulty: 1,
             externalip: None,
             block_template_cache_lifetime: None,
 
@@ -145,6 +181,7 @@ impl Default for Args {
             ram_scale: 1.0,
             retention_period_days: None,
             full_dataset: false,
+            snapshot: None,
         }
     }
 }
@@ -152,6 +189,8 @@ impl Default for Args {
 impl Args {
     pub fn apply_to_config(&self, config: &mut Config) {
         config.utxoindex = self.utxoindex;
+        config.txindex = self.txindex;
+        config.addresshistoryindex = self.addresshistoryindex;
         config.disable_upnp = self.disable_upnp;
         config.unsafe_rpc = self.unsafe_rpc;
         config.enable_unsynced_mining = self.enable_unsynced_mining;
@@ -186,6 +225,15 @@ impl Args {
             .collect()
     }
 
+    /// Builds the roles granted to the RPC clients according to their token
+    pub fn rpc_access_policy(&self) -> RpcResult<RpcAccessPolicy> {
+        RpcAccessPolicy::from_config(&self.rpc_roles, &self.rpc_tokens, self.rpc_anonymous_role.as_deref())
+    }
+
+    pub fn rpc_rate_limit(&self) -> RpcRateLimit {
+        RpcRateLimit::new(self.rpc_rate_limit, self.rpc_rate_burst)
+    }
+
     pub fn network(&self) -> NetworkId {
         match (self.testnet, self.devnet, self.simnet) {
             (false, false, false) => NetworkId::new(NetworkType::Mainnet),
@@ -208,6 +256,7 @@ pub fn cli() -> Command {
         .arg(arg!(-b --appdir <DATA_DIR> "Directory to store data."))
         .arg(arg!(--logdir <LOG_DIR> "Directory to log output."))
         .arg(arg!(--nologfiles "Disable logging to files."))
+        .arg(arg!(--backupdir <BACKUP_DIR> "Directory to store the database backups created by the CreateBackup RPC (preferably on the filesystem of the data directory)."))
         .arg(
             Arg::new("async_threads")
                 .short('t')
@@ -257,6 +306,48 @@ pub fn cli() -> Command {
                 .help("Interface:port to listen for wRPC JSON connections (default port: 44110, testnet: 44210)."),
         )
         .arg(arg!(--unsaferpc "Enable RPC commands which affect the state of the node"))
+        .arg(
+            Arg::new("rpc-tokens")
+                .long("rpc-token")
+                .value_name("ROLE:TOKEN")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Grant the RPC clients presenting TOKEN the role ROLE (public, miner, admin or a role defined by --rpc-role). gRPC clients send the token as a bearer `authorization` metadata, wRPC clients call Authenticate."),
+        )
+        .arg(
+            Arg::new("rpc-roles")
+                .long("rpc-role")
+                .value_name("NAME:ITEM[,ITEM...]")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Define an RPC role, each item adding the methods of a role, allowing a method (e.g. GetInfo or +SubmitTransaction) or denying a method (e.g. -GetMempoolEntries)."),
+        )
+        .arg(
+            Arg::new("rpc-anonymous-role")
+                .long("rpc-anonymous-role")
+                .value_name("ROLE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Role of the RPC clients presenting no token, `none` rejecting them (default: public if some --rpc-token is set, admin otherwise)."),
+        )
+        .arg(
+            Arg::new("rpc-rate-limit")
+                .long("rpc-rate-limit")
+                .value_name("UNITS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u32))
+                .help("Units of request cost granted per second to the RPC clients of an IP address, a request costing one unit per queried address, transaction or hundred headers (default: 0, unlimited)."),
+        )
+        .arg(
+            Arg::new("rpc-rate-burst")
+                .long("rpc-rate-burst")
+                .value_name("UNITS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u32))
+                .help("Units of request cost the RPC clients of an IP address may spend at once (default: 1000)."),
+        )
         .arg(
             Arg::new("connect-peers")
                 .long("connect")
@@ -317,6 +408,8 @@ pub fn cli() -> Command {
                 .help("Allow mainnet mining (currently enabled by default while the flag is kept for backwards compatibility)"),
         )
         .arg(arg!(--utxoindex "Enable the UTXO index"))
+        .arg(arg!(--txindex "Enable the transaction index"))
+        .arg(arg!(--addresshistoryindex "Enable the address history index"))
         .arg(
             Arg::new("max-tracked-addresses")
                 .long("max-tracked-addresses")
@@ -364,6 +457,41 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .value_parser(clap::value_parser!(u64))
                 .help("Interval in seconds for performance metrics collection."),
         )
+        .arg(
+            Arg::new("prometheus-listen")
+                .long("prometheus-listen")
+                .value_name("IP[:PORT]")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("127.0.0.1")
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Interface:port to serve metrics in the OpenMetrics text format at /metrics for Prometheus scraping (default: 127.0.0.1:9110)."),
+        )
+        .arg(
+            Arg::new("stratum-listen")
+                .long("stratum-listen")
+                .value_name("IP[:PORT]")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("0.0.0.0")
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Interface:port to run a Stratum v1 mining server on (default: 0.0.0.0:5555). Requires --stratum-pay-address."),
+        )
+        .arg(
+            Arg::new("stratum-pay-address")
+                .long("stratum-pay-address")
+                .value_name("ADDRESS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Address receiving the rewards of the blocks mined through the Stratum server."),
+        )
+        .arg(
+            Arg::new("stratum-difficulty")
+                .long("stratum-difficulty")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Share difficulty assigned to the Stratum miners, 1 standing for about 2^32 hashes per share (default: 1)."),
+        )
         .arg(arg!(--"disable-upnp" "Disable upnp"))
         .arg(arg!(--"nodnsseed" "Disable DNS seeding for peers"))
         .arg(arg!(--"nogrpc" "Disable gRPC server"))
@@ -391,6 +519,18 @@ a large RAM (~64GB) can set this value to ~3.0-4.0 and gain superior performance
         .arg(Arg::new("prealloc-address").long("prealloc-address").require_equals(true).value_parser(clap::value_parser!(String)))
         .arg(Arg::new("prealloc-amount").long("prealloc-amount").require_equals(true).value_parser(clap::value_parser!(u64)));
 
+    let cmd = cmd
+        .subcommand(
+            Command::new("export-snapshot")
+                .about("Export the pruning point proof, anticone and UTXO set of the stopped node to a snapshot file")
+                .arg(arg!(<FILE> "Path of the snapshot file").value_parser(clap::value_parser!(PathBuf))),
+        )
+        .subcommand(
+            Command::new("import-snapshot")
+                .about("Validate a snapshot file and bootstrap the stopped node from its pruning point")
+                .arg(arg!(<FILE> "Path of the snapshot file").value_parser(clap::value_parser!(PathBuf))),
+        );
+
     cmd
 }
 
@@ -426,11 +566,17 @@ impl Args {
         let args = Args {
             appdir: m.get_one::<String>("appdir").cloned().or(defaults.appdir),
             logdir: m.get_one::<String>("logdir").cloned().or(defaults.logdir),
+            backupdir: m.get_one::<String>("backupdir").cloned().or(defaults.backupdir),
             no_log_files: arg_match_unwrap_or::<bool>(&m, "nologfiles", defaults.no_log_files),
             rpclisten: m.get_one::<ContextualNetAddress>("rpclisten").cloned().or(defaults.rpclisten),
             rpclisten_borsh: m.get_one::<WrpcNetAddress>("rpclisten-borsh").cloned().or(defaults.rpclisten_borsh),
             rpclisten_json: m.get_one::<WrpcNetAddress>("rpclisten-json").cloned().or(defaults.rpclisten_json),
             unsafe_rpc: arg_match_unwrap_or::<bool>(&m, "unsaferpc", defaults.unsafe_rpc),
+            rpc_tokens: arg_match_many_unwrap_or::<String>(&m, "rpc-tokens", defaults.rpc_tokens),
+            rpc_roles: arg_match_many_unwrap_or::<String>(&m, "rpc-roles", defaults.rpc_roles),
+            rpc_anonymous_role: m.get_one::<String>("rpc-anonymous-role").cloned().or(defaults.rpc_anonymous_role),
+            rpc_rate_limit: arg_match_unwrap_or::<u32>(&m, "rpc-rate-limit", defaults.rpc_rate_limit),
+            rpc_rate_burst: arg_match_unwrap_or::<u32>(&m, "rpc-rate-burst", defaults.rpc_rate_burst),
             wrpc_verbose: false,
             log_level: arg_match_unwrap_or::<String>(&m, "log_level", defaults.log_level),
             async_threads: arg_match_unwrap_or::<usize>(&m, "async_threads", defaults.async_threads),
@@ -445,6 +591,8 @@ impl Args {
             enable_unsynced_mining: arg_match_unwrap_or::<bool>(&m, "enable-unsynced-mining", defaults.enable_unsynced_mining),
             enable_mainnet_mining: arg_match_unwrap_or::<bool>(&m, "enable-mainnet-mining", defaults.enable_mainnet_mining),
             utxoindex: arg_match_unwrap_or::<bool>(&m, "utxoindex", defaults.utxoindex),
+            txindex: arg_match_unwrap_or::<bool>(&m, "txindex", defaults.txindex),
+            addresshistoryindex: arg_match_unwrap_or::<bool>(&m, "addresshistoryindex", defaults.addresshistoryindex),
             testnet: arg_match_unwrap_or::<bool>(&m, "testnet", defaults.testnet),
             testnet_suffix: arg_match_unwrap_or::<u32>(&m, "netsuffix", defaults.testnet_suffix),
             devnet: arg_match_unwrap_or::<bool>(&m, "devnet", defaults.devnet),
@@ -456,6 +604,10 @@ impl Args {
             externalip: m.get_one::<ContextualNetAddress>("externalip").cloned(),
             perf_metrics: arg_match_unwrap_or::<bool>(&m, "perf-metrics", defaults.perf_metrics),
             perf_metrics_interval_sec: arg_match_unwrap_or::<u64>(&m, "perf-metrics-interval-sec", defaults.perf_metrics_interval_sec),
+            prometheus_listen: m.get_one::<ContextualNetAddress>("prometheus-listen").cloned().or(defaults.prometheus_listen),
+            stratum_listen: m.get_one::<ContextualNetAddress>("stratum-listen").cloned().or(defaults.stratum_listen),
+            stratum_pay_address: m.get_one::<String>("stratum-pay-address").cloned().or(defaults.stratum_pay_address),
+            stratum_difficulty: arg_match_unwrap_or::<u64>(&m, "stratum-difficulty", defaults.stratum_difficulty),
             // Note: currently used programmatically by benchmarks and not exposed to CLI users
             block_template_cache_lifetime: defaults.block_template_cache_lifetime,
             disable_upnp: arg_match_unwrap_or::<bool>(&m, "disable-upnp", defaults.disable_upnp),
@@ -464,6 +616,11 @@ impl Args {
             ram_scale: arg_match_unwrap_or::<f64>(&m, "ram-scale", defaults.ram_scale),
             retention_period_days: m.get_one::<f64>("retention-period-days").cloned().or(defaults.retention_period_days),
             full_dataset: arg_match_unwrap_or::<bool>(&m, "full-dataset", defaults.full_dataset),
+            snapshot: match m.subcommand() {
+                Some(("export-snapshot", m)) => m.get_one::<PathBuf>("FILE").cloned().map(SnapshotCommand::Export),
+                Some(("import-snapshot", m)) => m.get_one::<PathBuf>("FILE").cloned().map(SnapshotCommand::Import),
+                _ => None,
+            },
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: m.get_one::<u64>("num-prealloc-utxos").cloned(),
@@ -1,6 +1,7 @@
 use std::{fs, path::PathBuf, process::exit, sync::Arc, time::Duration};
 
 use async_channel::unbounded;
+use karlsen_addresses::{Address, Prefix};
 use karlsen_consensus_core::{
     config::ConfigBuilder,
     constants::TRANSIENT_BYTE_TO_MASS_FACTOR,
@@ -18,6 +19,7 @@ use karlsen_grpc_server::service::GrpcService;
 use karlsen_notify::{address::tracker::Tracker, subscription::context::SubscriptionContext};
 use karlsen_p2p_lib::Hub;
 use karlsen_p2p_mining::rule_engine::MiningRuleEngine;
+use karlsen_rpc_core::api::rate_limit::RpcRateLimiters;
 use karlsen_rpc_service::service::RpcCoreService;
 use karlsen_txscript::caches::TxScriptCacheCounters;
 use karlsen_utils::git;
@@ -42,7 +44,11 @@ use karlsen_mining::{
 use karlsen_p2p_flows::{flow_context::FlowContext, service::P2pService};
 
 use itertools::Itertools;
+use karlsen_addresshistory::{api::AddressHistoryProxy, AddressHistoryIndex};
 use karlsen_perf_monitor::{builder::Builder as PerfMonitorBuilder, counters::CountersSnapshot};
+use karlsen_prometheus::PrometheusService;
+use karlsen_stratum::StratumService;
+use karlsen_txindex::{api::TxIndexProxy, TxIndex};
 use karlsen_utxoindex::{api::UtxoIndexProxy, UtxoIndex};
 use karlsen_wrpc_server::service::{Options as WrpcServerOptions, WebSocketCounters as WrpcServerCounters, WrpcEncoding, WrpcService};
 
@@ -62,11 +68,13 @@ const ONE_GIGABYTE: f64 = 1_000_000_000.0;
 
 use crate::args::Args;
 
-const DEFAULT_DATA_DIR: &str = "datadir";
-const CONSENSUS_DB: &str = "consensus";
+pub(crate) const DEFAULT_DATA_DIR: &str = "datadir";
+pub(crate) const CONSENSUS_DB: &str = "consensus";
 const UTXOINDEX_DB: &str = "utxoindex";
-const META_DB: &str = "meta";
-const META_DB_FILE_LIMIT: i32 = 5;
+const TXINDEX_DB: &str = "txindex";
+const ADDRESSHISTORY_DB: &str = "addresshistory";
+pub(crate) const META_DB: &str = "meta";
+pub(crate) const META_DB_FILE_LIMIT: i32 = 5;
 const DEFAULT_LOG_DIR: &str = "logs";
 
 fn get_home_dir() -> PathBuf {
@@ -111,6 +119,17 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(Tracker::MAX_ADDRESS_UPPER_BOUND));
     }
+    if args.stratum_listen.is_some() {
+        let Some(pay_address) = args.stratum_pay_address.as_ref() else {
+            return Err(ConfigError::MissingStratumPayAddress);
+        };
+        if !Address::try_from(pay_address.as_str()).is_ok_and(|address| address.prefix == Prefix::from(args.network())) {
+            return Err(ConfigError::InvalidStratumPayAddress(pay_address.clone()));
+        }
+    }
+    if let Err(err) = args.rpc_access_policy() {
+        return Err(ConfigError::InvalidRpcAccessPolicy(err.to_string()));
+    }
     Ok(())
 }
 
@@ -172,6 +191,11 @@ pub fn get_log_dir(args: &Args) -> Option<String> {
     log_dir
 }
 
+/// Get the backup directory from the supplied [`Args`], if backups are enabled.
+pub fn get_backup_dir(args: &Args) -> Option<PathBuf> {
+    args.backupdir.as_ref().map(|backup_dir| PathBuf::from(backup_dir.replace('~', get_home_dir().as_path().to_str().unwrap())))
+}
+
 impl Runtime {
     pub fn from_args(args: &Args) -> Self {
         let log_dir = get_log_dir(args);
@@ -230,6 +254,20 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     } else {
         0
     };
+    let tx_files_limit = if args.txindex {
+        let tx_files_limit = fd_remaining / 10;
+        fd_remaining -= tx_files_limit;
+        tx_files_limit
+    } else {
+        0
+    };
+    let address_history_files_limit = if args.addresshistoryindex {
+        let address_history_files_limit = fd_remaining / 10;
+        fd_remaining -= address_history_files_limit;
+        address_history_files_limit
+    } else {
+        0
+    };
     // Make sure args forms a valid set of properties
     if let Err(err) = validate_args(args) {
         println!("{}", err);
@@ -263,6 +301,8 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
 
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
+    let txindex_db_dir = db_dir.join(TXINDEX_DB);
+    let addresshistory_db_dir = db_dir.join(ADDRESSHISTORY_DB);
     let meta_db_dir = db_dir.join(META_DB);
 
     let mut is_db_reset_needed = args.reset_db;
@@ -282,6 +322,14 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
         info!("Utxoindex Data directory {}", utxoindex_db_dir.display());
         fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
     }
+    if args.txindex {
+        info!("Txindex Data directory {}", txindex_db_dir.display());
+        fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+    }
+    if args.addresshistoryindex {
+        info!("Address history index Data directory {}", addresshistory_db_dir.display());
+        fs::create_dir_all(addresshistory_db_dir.as_path()).unwrap();
+    }
 
     if !args.archival && args.retention_period_days.is_some() {
         let retention_period_days = args.retention_period_days.unwrap();
@@ -469,6 +517,12 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
         if args.utxoindex {
             fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
         }
+        if args.txindex {
+            fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+        }
+        if args.addresshistoryindex {
+            fs::create_dir_all(addresshistory_db_dir.as_path()).unwrap();
+        }
 
         // Reopen the DB
         meta_db = karlsen_database::prelude::ConnBuilder::default()
@@ -491,6 +545,8 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
     let dns_seeders = if connect_peers.is_empty() && !args.disable_dns_seeding { config.dns_seeders } else { &[] };
 
     let grpc_server_addr = args.rpclisten.unwrap_or(ContextualNetAddress::unspecified()).normalize(config.default_rpc_port());
+    let prometheus_server_addr = args.prometheus_listen.map(|address| address.normalize(karlsen_prometheus::DEFAULT_PORT));
+    let stratum_server_addr = args.stratum_listen.map(|address| address.normalize(karlsen_stratum::DEFAULT_PORT));
 
     let core = Arc::new(Core::new());
 
@@ -530,7 +586,7 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
         tx_script_cache_counters.clone(),
         fd_remaining,
         mining_rules.clone(),
-        fish_context,
+        fish_context.clone(),
     ));
     let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
     let consensus_monitor = Arc::new(ConsensusMonitor::new(processing_counters.clone(), tick_service.clone()));
@@ -553,15 +609,34 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
     let system_info = SystemInfo::default();
 
     let notify_service = Arc::new(NotifyService::new(notification_root.clone(), notification_recv, subscription_context.clone()));
-    let index_service: Option<Arc<IndexService>> = if args.utxoindex {
+    let index_service: Option<Arc<IndexService>> = if args.utxoindex || args.txindex || args.addresshistoryindex {
         // Use only a single thread for none-consensus databases
-        let utxoindex_db = karlsen_database::prelude::ConnBuilder::default()
-            .with_db_path(utxoindex_db_dir)
-            .with_files_limit(utxo_files_limit)
-            .build()
-            .unwrap();
-        let utxoindex = UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap());
-        let index_service = Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), Some(utxoindex)));
+        let utxoindex = args.utxoindex.then(|| {
+            let utxoindex_db = karlsen_database::prelude::ConnBuilder::default()
+                .with_db_path(utxoindex_db_dir)
+                .with_files_limit(utxo_files_limit)
+                .build()
+                .unwrap();
+            UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap())
+        });
+        let txindex = args.txindex.then(|| {
+            let txindex_db = karlsen_database::prelude::ConnBuilder::default()
+                .with_db_path(txindex_db_dir)
+                .with_files_limit(tx_files_limit)
+                .build()
+                .unwrap();
+            TxIndexProxy::new(TxIndex::new(consensus_manager.clone(), txindex_db).unwrap())
+        });
+        let addresshistory = args.addresshistoryindex.then(|| {
+            let addresshistory_db = karlsen_database::prelude::ConnBuilder::default()
+                .with_db_path(addresshistory_db_dir)
+                .with_files_limit(address_history_files_limit)
+                .build()
+                .unwrap();
+            AddressHistoryProxy::new(AddressHistoryIndex::new(consensus_manager.clone(), addresshistory_db).unwrap())
+        });
+        let index_service =
+            Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), utxoindex, txindex, addresshistory));
         Some(index_service)
     } else {
         None
@@ -619,14 +694,16 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
     let rpc_core_service = Arc::new(RpcCoreService::new(
         consensus_manager.clone(),
         notify_service.notifier(),
-        index_service.as_ref().map(|x| x.notifier()),
+        index_service.as_ref().filter(|x| x.utxoindex().is_some()).map(|x| x.notifier()),
         mining_manager,
-        flow_context,
+        flow_context.clone(),
         subscription_context,
-        index_service.as_ref().map(|x| x.utxoindex().unwrap()),
+        index_service.as_ref().and_then(|x| x.utxoindex()),
+        index_service.as_ref().and_then(|x| x.txindex()),
+        index_service.as_ref().and_then(|x| x.addresshistory()),
         config.clone(),
         core.clone(),
-        processing_counters,
+        processing_counters.clone(),
         wrpc_borsh_counters.clone(),
         wrpc_json_counters.clone(),
         perf_monitor.clone(),
@@ -634,7 +711,10 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
         grpc_tower_counters.clone(),
         system_info,
         mining_rule_engine.clone(),
+        get_backup_dir(args),
     ));
+    let rpc_access_policy = Arc::new(args.rpc_access_policy().expect("the RPC access policy was validated"));
+    let rpc_rate_limiters = Arc::new(RpcRateLimiters::new(args.rpc_rate_limit()));
     let grpc_service_broadcasters: usize = 3; // TODO: add a command line argument or derive from other arg/config/host-related fields
     let grpc_service = if !args.disable_grpc {
         Some(Arc::new(GrpcService::new(
@@ -644,10 +724,26 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
             args.rpc_max_clients,
             grpc_service_broadcasters,
             grpc_tower_counters,
+            rpc_access_policy.clone(),
+            rpc_rate_limiters.clone(),
         )))
     } else {
         None
     };
+    let prometheus_service =
+        prometheus_server_addr.map(|address| Arc::new(PrometheusService::new(address, rpc_core_service.clone(), processing_counters)));
+    let stratum_service = stratum_server_addr.map(|address| {
+        // The pay address was checked by `validate_args`
+        let pay_address = Address::try_from(args.stratum_pay_address.as_deref().unwrap()).unwrap();
+        Arc::new(StratumService::new(
+            address,
+            pay_address,
+            args.stratum_difficulty,
+            flow_context,
+            rpc_core_service.clone(),
+            fish_context,
+        ))
+    });
 
     // Create an async runtime and register the top-level async services
     let async_runtime = Arc::new(AsyncRuntime::new(args.async_threads));
@@ -663,6 +759,12 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
     if let Some(grpc_service) = grpc_service {
         async_runtime.register(grpc_service)
     }
+    if let Some(prometheus_service) = prometheus_service {
+        async_runtime.register(prometheus_service)
+    }
+    if let Some(stratum_service) = stratum_service {
+        async_runtime.register(stratum_service)
+    }
     async_runtime.register(p2p_service);
     async_runtime.register(consensus_monitor);
     async_runtime.register(mining_monitor);
@@ -686,6 +788,8 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
                 WrpcServerOptions {
                     listen_address: listen_address.to_address(&network.network_type, &encoding).to_string(), // TODO: use a normalized ContextualNetAddress instead of a String
                     verbose: args.wrpc_verbose,
+                    access_policy: rpc_access_policy.clone(),
+                    rate_limiters: rpc_rate_limiters.clone(),
                     ..WrpcServerOptions::default()
                 },
             ))
@@ -9,7 +9,8 @@ use karlsen_core::{info, signals::Signals};
 use karlsen_utils::fd_budget;
 use karlsend_lib::{
     args::parse_args,
-    daemon::{create_core, DESIRED_DAEMON_SOFT_FD_LIMIT, MINIMUM_DAEMON_SOFT_FD_LIMIT},
+    daemon::{create_core, Runtime, DESIRED_DAEMON_SOFT_FD_LIMIT, MINIMUM_DAEMON_SOFT_FD_LIMIT},
+    snapshot,
 };
 
 #[cfg(feature = "heap")]
@@ -24,6 +25,15 @@ pub fn main() {
 
     let args = parse_args();
 
+    if let Some(command) = args.snapshot.clone() {
+        let _runtime = Runtime::from_args(&args);
+        if let Err(err) = snapshot::run(&args, command) {
+            println!("{err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     match fd_budget::try_set_fd_limit(DESIRED_DAEMON_SOFT_FD_LIMIT) {
         Ok(limit) => {
             if limit < MINIMUM_DAEMON_SOFT_FD_LIMIT {
@@ -0,0 +1,513 @@
+//!
+//! Offline export and import of the pruning point state.
+//!
+//! A snapshot holds the artifacts a syncing node downloads from its peers during an IBD with headers
+//! proof: the pruning point proof, the past pruning points, the pruning point anticone along with its
+//! trusted data and the pruning point UTXO set. Importing a snapshot validates them just like the IBD
+//! does, in a staging consensus committed only once the UTXO set matches the pruning point commitment.
+//! The node then syncs the future of the pruning point from its peers.
+//!
+
+use crate::{
+    args::Args,
+    daemon::{get_app_dir_from_args, CONSENSUS_DB, DEFAULT_DATA_DIR, META_DB, META_DB_FILE_LIMIT},
+};
+use itertools::Itertools;
+use karlsen_consensus::{
+    consensus::factory::{Factory as ConsensusFactory, MultiConsensusManagementStore},
+    pipeline::ProcessingCounters,
+};
+use karlsen_consensus_core::{
+    api::ConsensusApi,
+    block::Block,
+    config::{Config, ConfigBuilder},
+    errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError},
+    header::Header,
+    mining_rules::MiningRules,
+    muhash::MuHashExtensions,
+    pruning::{PruningPointProof, PruningPointsList, PruningProofMetadata},
+    trusted::{TrustedGhostdagData, TrustedHeader},
+    tx::{Transaction, TransactionOutpoint, UtxoEntry},
+    BlueWorkType,
+};
+use karlsen_consensus_notify::root::ConsensusNotificationRoot;
+use karlsen_consensusmanager::{ConsensusFactory as _, ConsensusInstance, DynConsensusCtl};
+use karlsen_core::info;
+use karlsen_hashes::{pow_hashers::FishHashContext, Hash};
+use karlsen_muhash::MuHash;
+use karlsen_p2p_lib::{
+    common::ProtocolError,
+    convert::model::trusted::{TrustedDataEntry, TrustedDataPackage},
+};
+use karlsen_txscript::caches::TxScriptCacheCounters;
+use karlsen_utils::fd_budget;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread::JoinHandle,
+};
+use thiserror::Error;
+
+/// Leading bytes of a snapshot file
+const SNAPSHOT_MAGIC: &[u8; 8] = b"KLSSNAP\0";
+
+/// Version of the snapshot file format
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// Number of UTXO entries per chunk of the snapshot file
+const UTXO_CHUNK_SIZE: usize = 1000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SnapshotCommand {
+    /// Export the pruning point state of the node to a snapshot file
+    Export(PathBuf),
+    /// Bootstrap the node from a snapshot file
+    Import(PathBuf),
+}
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("snapshot I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("snapshot encoding error: {0}")]
+    Encoding(#[from] bincode::Error),
+
+    #[error("not a karlsend snapshot file")]
+    InvalidMagic,
+
+    #[error("unsupported snapshot version {0}, expected {SNAPSHOT_VERSION}")]
+    UnsupportedVersion(u32),
+
+    #[error("the snapshot was exported from network {0} while the node runs on {1}")]
+    NetworkMismatch(String, String),
+
+    #[error("no database found at {0}")]
+    MissingDatabase(String),
+
+    #[error("the database is from a different karlsend version, start the node once to upgrade it")]
+    DatabaseVersion,
+
+    #[error("the pruning point is still the genesis block, there is nothing to export")]
+    NothingToExport,
+
+    #[error("invalid snapshot: {0}")]
+    InvalidSnapshot(&'static str),
+
+    #[error("the snapshot holds {0} UTXO entries while its trailer records {1}")]
+    UtxoCountMismatch(u64, u64),
+
+    #[error("the exported UTXO set hashes to {1} while the pruning point commits to {0}")]
+    UtxoCommitmentMismatch(Hash, Hash),
+
+    #[error(transparent)]
+    Consensus(#[from] ConsensusError),
+
+    #[error(transparent)]
+    PruningImport(#[from] PruningImportError),
+
+    #[error(transparent)]
+    Rule(#[from] RuleError),
+
+    #[error(transparent)]
+    Protocol(#[from] ProtocolError),
+
+    #[error(transparent)]
+    FdBudget(#[from] fd_budget::Error),
+}
+
+pub type SnapshotResult<T> = std::result::Result<T, SnapshotError>;
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotHeader {
+    version: u32,
+    network_id: String,
+    pruning_point: Hash,
+    /// Blue work of the headers selected tip of the exporting node, the claimed work the proof is validated against
+    headers_selected_tip_blue_work: BlueWorkType,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotBlock {
+    header: Arc<Header>,
+    transactions: Vec<Transaction>,
+}
+
+impl From<Block> for SnapshotBlock {
+    fn from(block: Block) -> Self {
+        Self { header: block.header, transactions: (*block.transactions).clone() }
+    }
+}
+
+impl From<SnapshotBlock> for Block {
+    fn from(block: SnapshotBlock) -> Self {
+        Block::new(Header::clone(&block.header), block.transactions)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotTrailer {
+    utxo_count: u64,
+}
+
+struct SnapshotWriter<W: Write> {
+    inner: W,
+}
+
+impl<W: Write> SnapshotWriter<W> {
+    fn new(mut inner: W) -> SnapshotResult<Self> {
+        inner.write_all(SNAPSHOT_MAGIC)?;
+        Ok(Self { inner })
+    }
+
+    /// Writes `item` as a record prefixed by its length
+    fn write<T: Serialize + ?Sized>(&mut self, item: &T) -> SnapshotResult<()> {
+        let record = bincode::serialize(item)?;
+        self.inner.write_all(&(record.len() as u64).to_le_bytes())?;
+        self.inner.write_all(&record)?;
+        Ok(())
+    }
+
+    fn finish(mut self) -> SnapshotResult<W> {
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+struct SnapshotReader<R: Read> {
+    inner: R,
+    record: Vec<u8>,
+}
+
+impl<R: Read> SnapshotReader<R> {
+    fn new(mut inner: R) -> SnapshotResult<Self> {
+        let mut magic = [0u8; SNAPSHOT_MAGIC.len()];
+        inner.read_exact(&mut magic).map_err(|_| SnapshotError::InvalidMagic)?;
+        if &magic != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::InvalidMagic);
+        }
+        Ok(Self { inner, record: Vec::new() })
+    }
+
+    /// Reads the next record, decoded from a buffer since some types only deserialize from borrowed bytes
+    fn read<T: DeserializeOwned>(&mut self) -> SnapshotResult<T> {
+        let mut len = [0u8; size_of::<u64>()];
+        self.inner.read_exact(&mut len)?;
+        self.record.resize(u64::from_le_bytes(len) as usize, 0);
+        self.inner.read_exact(&mut self.record)?;
+        Ok(bincode::deserialize(&self.record)?)
+    }
+
+    fn read_header(&mut self, config: &Config) -> SnapshotResult<SnapshotHeader> {
+        let header: SnapshotHeader = self.read()?;
+        if header.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(header.version));
+        }
+        let network_id = config.net.to_string();
+        if header.network_id != network_id {
+            return Err(SnapshotError::NetworkMismatch(header.network_id, network_id));
+        }
+        Ok(header)
+    }
+}
+
+/// Runs a snapshot command against the database of the node configured by `args`. The node must be stopped.
+pub fn run(args: &Args, command: SnapshotCommand) -> SnapshotResult<()> {
+    let config = Arc::new(
+        ConfigBuilder::new(args.network().into())
+            .adjust_perf_params_to_consensus_params()
+            .apply_args(|config| args.apply_to_config(config))
+            .build(),
+    );
+    let db_dir = get_app_dir_from_args(args).join(args.network().to_prefixed()).join(DEFAULT_DATA_DIR);
+    // Consensus notifications are not consumed, the receiver is only held to keep the notification root open
+    let (notification_send, _notification_recv) = async_channel::unbounded();
+    let notification_root = Arc::new(ConsensusNotificationRoot::new(notification_send));
+    match command {
+        SnapshotCommand::Export(path) => {
+            if !db_dir.join(META_DB).exists() {
+                return Err(SnapshotError::MissingDatabase(db_dir.display().to_string()));
+            }
+            let factory = open_consensus_factory(args, &config, &db_dir, notification_root)?;
+            let (consensus, _) = factory.new_active_consensus();
+            let result = export_snapshot(&consensus, &config, &path);
+            drop(consensus);
+            factory.close();
+            result
+        }
+        SnapshotCommand::Import(path) => {
+            // Reject foreign files before the costlier opening of the databases
+            let mut reader = SnapshotReader::new(BufReader::new(File::open(&path)?))?;
+            let header = reader.read_header(&config)?;
+            fs::create_dir_all(db_dir.join(CONSENSUS_DB))?;
+            fs::create_dir_all(db_dir.join(META_DB))?;
+            let factory = open_consensus_factory(args, &config, &db_dir, notification_root)?;
+            info!("Importing the state of pruning point {} from {}", header.pruning_point, path.display());
+            let result = import_snapshot(&factory, &config, &mut reader, header);
+            factory.close();
+            result
+        }
+    }
+}
+
+fn open_consensus_factory(
+    args: &Args,
+    config: &Arc<Config>,
+    db_dir: &Path,
+    notification_root: Arc<ConsensusNotificationRoot>,
+) -> SnapshotResult<Arc<ConsensusFactory>> {
+    let meta_db = karlsen_database::prelude::ConnBuilder::default()
+        .with_db_path(db_dir.join(META_DB))
+        .with_files_limit(META_DB_FILE_LIMIT)
+        .build()?;
+    if MultiConsensusManagementStore::new(meta_db.clone()).should_upgrade().unwrap() {
+        return Err(SnapshotError::DatabaseVersion);
+    }
+
+    Ok(Arc::new(ConsensusFactory::new(
+        meta_db,
+        config,
+        db_dir.join(CONSENSUS_DB),
+        num_cpus::get(),
+        notification_root,
+        Arc::new(ProcessingCounters::default()),
+        Arc::new(TxScriptCacheCounters::default()),
+        fd_budget::limit() - META_DB_FILE_LIMIT,
+        Arc::new(MiningRules::default()),
+        Arc::new(FishHashContext::new(args.full_dataset, None)),
+    )))
+}
+
+fn export_snapshot(consensus: &ConsensusInstance, config: &Config, path: &Path) -> SnapshotResult<()> {
+    let session = consensus.unguarded_session_blocking();
+    let pruning_point = session.pruning_point();
+    if pruning_point == config.genesis.hash {
+        return Err(SnapshotError::NothingToExport);
+    }
+    info!("Exporting the state of pruning point {} to {}", pruning_point, path.display());
+
+    // The file is only moved to its final path once the UTXO set was verified against the pruning point commitment
+    let partial_path = PathBuf::from(format!("{}.partial", path.display()));
+    let result = write_snapshot(&*session, config, pruning_point, &partial_path).and_then(|()| Ok(fs::rename(&partial_path, path)?));
+    if result.is_err() {
+        let _ = fs::remove_file(&partial_path);
+    }
+    result
+}
+
+fn write_snapshot(consensus: &dyn ConsensusApi, config: &Config, pruning_point: Hash, path: &Path) -> SnapshotResult<()> {
+    let mut writer = SnapshotWriter::new(BufWriter::new(File::create(path)?))?;
+    writer.write(&SnapshotHeader {
+        version: SNAPSHOT_VERSION,
+        network_id: config.net.to_string(),
+        pruning_point,
+        headers_selected_tip_blue_work: consensus.get_header(consensus.get_headers_selected_tip())?.blue_work,
+    })?;
+
+    let proof = consensus.get_pruning_point_proof();
+    info!("Exporting the pruning point proof with {} headers", proof.iter().map(|level| level.len()).sum::<usize>());
+    writer.write(&*proof)?;
+    writer.write(&consensus.pruning_point_headers())?;
+
+    let trusted_data = consensus.get_pruning_point_anticone_and_trusted_data()?;
+    writer.write(&trusted_data.daa_window_blocks)?;
+    writer.write(&trusted_data.ghostdag_blocks)?;
+    let anticone =
+        trusted_data.anticone.iter().map(|&hash| consensus.get_block(hash).map(SnapshotBlock::from)).try_collect::<_, Vec<_>, _>()?;
+    info!("Exporting the pruning point and its anticone of {} blocks", anticone.len());
+    writer.write(&anticone)?;
+
+    let mut multiset = MuHash::new();
+    let mut utxo_count = 0u64;
+    let mut from_outpoint = None;
+    loop {
+        let chunk = consensus.get_pruning_point_utxos(pruning_point, from_outpoint, UTXO_CHUNK_SIZE, from_outpoint.is_some())?;
+        chunk.iter().for_each(|(outpoint, entry)| multiset.add_utxo(outpoint, entry));
+        utxo_count += chunk.len() as u64;
+        if !chunk.is_empty() {
+            writer.write(&chunk)?;
+        }
+        // A chunk smaller than requested indicates that there are no more entries to query
+        if chunk.len() < UTXO_CHUNK_SIZE {
+            break;
+        }
+        from_outpoint = Some(chunk.last().expect("not empty by prev condition").0);
+    }
+    // An empty chunk marks the end of the UTXO set
+    writer.write(&Vec::<(TransactionOutpoint, UtxoEntry)>::new())?;
+    writer.write(&SnapshotTrailer { utxo_count })?;
+    writer.finish()?.into_inner().map_err(|err| err.into_error())?.sync_all()?;
+
+    let utxo_commitment = consensus.get_header(pruning_point)?.utxo_commitment;
+    let multiset_hash = multiset.finalize();
+    if multiset_hash != utxo_commitment {
+        return Err(SnapshotError::UtxoCommitmentMismatch(utxo_commitment, multiset_hash));
+    }
+    info!("Exported {} UTXO entries of pruning point {}", utxo_count, pruning_point);
+    Ok(())
+}
+
+fn import_snapshot<R: Read>(
+    factory: &ConsensusFactory,
+    config: &Config,
+    reader: &mut SnapshotReader<R>,
+    header: SnapshotHeader,
+) -> SnapshotResult<()> {
+    // The snapshot is validated in the context of the current consensus and applied to a staging consensus,
+    // only committed once fully imported
+    let (consensus, _) = factory.new_active_consensus();
+    let (staging, staging_ctl) = factory.new_staging_consensus();
+    let handles = staging_ctl.start();
+    let result = apply_snapshot(&consensus, &staging, reader, header, config);
+    drop(consensus);
+    if result.is_ok() {
+        staging_ctl.make_active();
+    }
+    stop_consensus(staging, staging_ctl, handles);
+    match result {
+        Ok(()) => {
+            factory.delete_inactive_consensus_entries();
+            info!("Snapshot imported, the node will sync the future of the pruning point from its peers");
+            Ok(())
+        }
+        Err(err) => {
+            factory.delete_staging_entry();
+            Err(err)
+        }
+    }
+}
+
+fn stop_consensus(consensus: ConsensusInstance, ctl: DynConsensusCtl, handles: Vec<JoinHandle<()>>) {
+    ctl.stop();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    // Drop the instance and its DB refs so that its entry can be deleted
+    drop(consensus);
+}
+
+fn apply_snapshot<R: Read>(
+    consensus: &ConsensusInstance,
+    staging: &ConsensusInstance,
+    reader: &mut SnapshotReader<R>,
+    header: SnapshotHeader,
+    config: &Config,
+) -> SnapshotResult<()> {
+    let consensus = consensus.unguarded_session_blocking();
+    let staging = staging.unguarded_session_blocking();
+
+    let proof: PruningPointProof = reader.read()?;
+    info!("Validating the pruning point proof with {} headers", proof.iter().map(|level| level.len()).sum::<usize>());
+    consensus.validate_pruning_proof(&proof, &PruningProofMetadata::new(header.headers_selected_tip_blue_work))?;
+    let proof_pruning_point = proof[0].last().expect("was just ensured by validation").hash;
+    if proof_pruning_point != header.pruning_point {
+        return Err(SnapshotError::InvalidSnapshot("the proof pruning point is not the snapshot pruning point"));
+    }
+    if proof_pruning_point == config.genesis.hash {
+        return Err(SnapshotError::InvalidSnapshot("the proof pruning point is the genesis block"));
+    }
+    if proof_pruning_point == consensus.pruning_point() {
+        return Err(SnapshotError::InvalidSnapshot("the proof pruning point is the same as the current pruning point"));
+    }
+
+    let pruning_points: PruningPointsList = reader.read()?;
+    if pruning_points.last().map(|header| header.hash) != Some(proof_pruning_point) {
+        return Err(SnapshotError::InvalidSnapshot("the proof pruning point is not equal to the last pruning point in the list"));
+    }
+    if pruning_points.first().map(|header| header.hash) != Some(config.genesis.hash) {
+        return Err(SnapshotError::InvalidSnapshot("the first pruning point in the list is expected to be genesis"));
+    }
+    if consensus.are_pruning_points_violating_finality(pruning_points.clone()) {
+        return Err(SnapshotError::InvalidSnapshot("pruning points are violating finality"));
+    }
+
+    let daa_window: Vec<TrustedHeader> = reader.read()?;
+    let ghostdag_window: Vec<TrustedGhostdagData> = reader.read()?;
+    let anticone: Vec<SnapshotBlock> = reader.read()?;
+    if anticone.first().map(|block| block.header.hash) != Some(proof_pruning_point) {
+        return Err(SnapshotError::InvalidSnapshot("the proof pruning point is not equal to the first trusted block"));
+    }
+    let entries = anticone.into_iter().map(|block| TrustedDataEntry::new(block.into(), vec![], vec![])).collect();
+    let trusted_set = TrustedDataPackage::new(daa_window, ghostdag_window).build_trusted_subdag(entries)?;
+
+    staging.apply_pruning_proof(proof, &trusted_set)?;
+    staging.import_pruning_points(pruning_points)?;
+
+    info!("Processing {} trusted blocks", trusted_set.len());
+    let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+    for tb in trusted_set {
+        runtime.block_on(staging.validate_and_insert_trusted_block(tb).virtual_state_task)?;
+    }
+
+    let mut multiset = MuHash::new();
+    let mut utxo_count = 0u64;
+    loop {
+        let chunk: Vec<(TransactionOutpoint, UtxoEntry)> = reader.read()?;
+        if chunk.is_empty() {
+            break;
+        }
+        staging.append_imported_pruning_point_utxos(&chunk, &mut multiset);
+        utxo_count += chunk.len() as u64;
+    }
+    let trailer: SnapshotTrailer = reader.read()?;
+    if trailer.utxo_count != utxo_count {
+        return Err(SnapshotError::UtxoCountMismatch(utxo_count, trailer.utxo_count));
+    }
+    info!("Validating the {} UTXO entries of pruning point {}", utxo_count, proof_pruning_point);
+    staging.import_pruning_point_utxo_set(proof_pruning_point, multiset)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_consensus_core::config::params::SIMNET_PARAMS;
+
+    fn header(network_id: &str) -> SnapshotHeader {
+        SnapshotHeader {
+            version: SNAPSHOT_VERSION,
+            network_id: network_id.to_owned(),
+            pruning_point: 1.into(),
+            headers_selected_tip_blue_work: 1000.into(),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_encoding() {
+        let config = Config::new(SIMNET_PARAMS);
+
+        let mut writer = SnapshotWriter::new(Vec::new()).unwrap();
+        writer.write(&header(&config.net.to_string())).unwrap();
+        writer.write(&vec![(TransactionOutpoint::new(2.into(), 0), UtxoEntry::new(10, Default::default(), 0, false))]).unwrap();
+        writer.write(&SnapshotTrailer { utxo_count: 1 }).unwrap();
+        let bytes = writer.finish().unwrap();
+
+        let mut reader = SnapshotReader::new(bytes.as_slice()).unwrap();
+        assert_eq!(reader.read_header(&config).unwrap().pruning_point, 1.into());
+        let chunk: Vec<(TransactionOutpoint, UtxoEntry)> = reader.read().unwrap();
+        assert_eq!(chunk[0].1.amount, 10);
+        assert_eq!(reader.read::<SnapshotTrailer>().unwrap().utxo_count, 1);
+
+        // A truncated snapshot fails to decode
+        let mut reader = SnapshotReader::new(&bytes[..bytes.len() - 1]).unwrap();
+        reader.read_header(&config).unwrap();
+        reader.read::<Vec<(TransactionOutpoint, UtxoEntry)>>().unwrap();
+        assert!(matches!(reader.read::<SnapshotTrailer>(), Err(SnapshotError::Io(_))));
+
+        assert!(matches!(SnapshotReader::new(&b"KLSSNAP"[..]), Err(SnapshotError::InvalidMagic)));
+        assert!(matches!(SnapshotReader::new(&bytes[1..]), Err(SnapshotError::InvalidMagic)));
+    }
+
+    #[test]
+    fn test_snapshot_network() {
+        let config = Config::new(SIMNET_PARAMS);
+        let mut writer = SnapshotWriter::new(Vec::new()).unwrap();
+        writer.write(&header("mainnet")).unwrap();
+        let bytes = writer.finish().unwrap();
+        let mut reader = SnapshotReader::new(bytes.as_slice()).unwrap();
+        assert!(matches!(reader.read_header(&config), Err(SnapshotError::NetworkMismatch(..))));
+    }
+}
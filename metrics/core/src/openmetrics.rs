@@ -0,0 +1,187 @@
+use crate::data::{Metric, MetricsSnapshot};
+use std::fmt::Write;
+
+/// Content type of a response carrying metrics in the OpenMetrics text format.
+pub const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Prefix of all the metric family names exported by the node.
+pub const OPENMETRICS_PREFIX: &str = "karlsen";
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum OpenMetricsType {
+    /// A monotonically increasing total
+    Counter,
+    /// A value which can arbitrarily go up and down
+    Gauge,
+}
+
+impl OpenMetricsType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OpenMetricsType::Counter => "counter",
+            OpenMetricsType::Gauge => "gauge",
+        }
+    }
+}
+
+impl Metric {
+    /// OpenMetrics type of the metric.
+    ///
+    /// Returns `None` for rates computed between two samples, since scrapers derive those from the matching counters.
+    pub fn openmetrics_type(&self) -> Option<OpenMetricsType> {
+        match self {
+            Metric::NodeDiskIoReadPerSec
+            | Metric::NodeDiskIoWritePerSec
+            | Metric::NodeTotalBytesTxPerSecond
+            | Metric::NodeTotalBytesRxPerSecond
+            | Metric::NodeP2pBytesTxPerSecond
+            | Metric::NodeP2pBytesRxPerSecond
+            | Metric::NodeBorshBytesTxPerSecond
+            | Metric::NodeBorshBytesRxPerSecond
+            | Metric::NodeGrpcUserBytesTxPerSecond
+            | Metric::NodeGrpcUserBytesRxPerSecond
+            | Metric::NodeJsonBytesTxPerSecond
+            | Metric::NodeJsonBytesRxPerSecond
+            | Metric::NetworkTransactionsPerSecond => None,
+            // --
+            Metric::NodeDiskIoReadBytes
+            | Metric::NodeDiskIoWriteBytes
+            | Metric::NodeBorshConnectionAttempts
+            | Metric::NodeBorshHandshakeFailures
+            | Metric::NodeJsonConnectionAttempts
+            | Metric::NodeJsonHandshakeFailures
+            | Metric::NodeTotalBytesTx
+            | Metric::NodeTotalBytesRx
+            | Metric::NodeP2pBytesTx
+            | Metric::NodeP2pBytesRx
+            | Metric::NodeBorshBytesTx
+            | Metric::NodeBorshBytesRx
+            | Metric::NodeGrpcUserBytesTx
+            | Metric::NodeGrpcUserBytesRx
+            | Metric::NodeJsonBytesTx
+            | Metric::NodeJsonBytesRx
+            | Metric::NodeBlocksSubmittedCount
+            | Metric::NodeHeadersProcessedCount
+            | Metric::NodeDependenciesProcessedCount
+            | Metric::NodeBodiesProcessedCount
+            | Metric::NodeTransactionsProcessedCount
+            | Metric::NodeChainBlocksProcessedCount
+            | Metric::NodeMassProcessedCount => Some(OpenMetricsType::Counter),
+            // --
+            Metric::NodeCpuUsage
+            | Metric::NodeResidentSetSizeBytes
+            | Metric::NodeVirtualMemorySizeBytes
+            | Metric::NodeFileHandlesCount
+            | Metric::NodeStorageSizeBytes
+            | Metric::NodeActivePeers
+            | Metric::NodeBorshLiveConnections
+            | Metric::NodeJsonLiveConnections
+            | Metric::NodeDatabaseBlocksCount
+            | Metric::NodeDatabaseHeadersCount
+            | Metric::NetworkMempoolSize
+            | Metric::NetworkTipHashesCount
+            | Metric::NetworkDifficulty
+            | Metric::NetworkPastMedianTime
+            | Metric::NetworkVirtualParentHashesCount
+            | Metric::NetworkVirtualDaaScore => Some(OpenMetricsType::Gauge),
+        }
+    }
+
+    /// OpenMetrics family name of the metric without the [`OPENMETRICS_PREFIX`],
+    /// i.e. `node_p2p_bytes_tx` for [`Metric::NodeP2pBytesTx`].
+    pub fn openmetrics_name(&self) -> String {
+        let mut name = String::new();
+        for (i, c) in format!("{self:?}").chars().enumerate() {
+            if c.is_ascii_uppercase() {
+                if i > 0 {
+                    name.push('_');
+                }
+                name.push(c.to_ascii_lowercase());
+            } else {
+                name.push(c);
+            }
+        }
+        name
+    }
+}
+
+/// Builds an exposition in the OpenMetrics text format.
+///
+/// Each metric is written as its own family, and the exposition gets terminated by [`OpenMetricsEncoder::finish`].
+#[derive(Default)]
+pub struct OpenMetricsEncoder {
+    buffer: String,
+}
+
+impl OpenMetricsEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes all the metrics of `snapshot` having an [`OpenMetricsType`].
+    pub fn snapshot(&mut self, snapshot: &MetricsSnapshot) {
+        for metric in Metric::into_iter() {
+            if let Some(kind) = metric.openmetrics_type() {
+                self.family(&metric.openmetrics_name(), metric.title().0, kind, snapshot.get(&metric));
+            }
+        }
+    }
+
+    pub fn counter(&mut self, name: &str, help: &str, value: f64) {
+        self.family(name, help, OpenMetricsType::Counter, value);
+    }
+
+    pub fn gauge(&mut self, name: &str, help: &str, value: f64) {
+        self.family(name, help, OpenMetricsType::Gauge, value);
+    }
+
+    fn family(&mut self, name: &str, help: &str, kind: OpenMetricsType, value: f64) {
+        // Samples of counters carry a `_total` suffix
+        let suffix = if kind == OpenMetricsType::Counter { "_total" } else { "" };
+        let value = match value {
+            v if v == f64::INFINITY => "+Inf".to_string(),
+            v if v == f64::NEG_INFINITY => "-Inf".to_string(),
+            v => v.to_string(),
+        };
+        writeln!(self.buffer, "# TYPE {OPENMETRICS_PREFIX}_{name} {}", kind.as_str()).unwrap();
+        writeln!(self.buffer, "# HELP {OPENMETRICS_PREFIX}_{name} {help}").unwrap();
+        writeln!(self.buffer, "{OPENMETRICS_PREFIX}_{name}{suffix} {value}").unwrap();
+    }
+
+    /// Terminates the exposition and returns it.
+    pub fn finish(mut self) -> String {
+        self.buffer.push_str("# EOF\n");
+        self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::MetricsData;
+
+    #[test]
+    fn test_openmetrics_names() {
+        assert_eq!(Metric::NodeP2pBytesTx.openmetrics_name(), "node_p2p_bytes_tx");
+        assert_eq!(Metric::NetworkVirtualDaaScore.openmetrics_name(), "network_virtual_daa_score");
+    }
+
+    #[test]
+    fn test_openmetrics_encoding() {
+        let data = MetricsData { node_p2p_bytes_tx: 1024, network_virtual_daa_score: 42, ..MetricsData::new(1000.0) };
+        let mut encoder = OpenMetricsEncoder::new();
+        encoder.snapshot(&MetricsSnapshot::from((&data, &data)));
+        encoder.gauge("custom_ratio", "Custom Ratio", f64::INFINITY);
+        let exposition = encoder.finish();
+
+        assert!(exposition.contains("# TYPE karlsen_node_p2p_bytes_tx counter\n"));
+        assert!(exposition.contains("# HELP karlsen_node_p2p_bytes_tx p2p Tx\n"));
+        assert!(exposition.contains("\nkarlsen_node_p2p_bytes_tx_total 1024\n"));
+        assert!(exposition.contains("# TYPE karlsen_network_virtual_daa_score gauge\n"));
+        assert!(exposition.contains("\nkarlsen_network_virtual_daa_score 42\n"));
+        assert!(exposition.contains("\nkarlsen_custom_ratio +Inf\n"));
+        // Rates are left to the scraper
+        assert!(!exposition.contains("per_second"));
+        assert!(exposition.ends_with("\n# EOF\n"));
+    }
+}
@@ -0,0 +1,126 @@
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::{body::Incoming, header::CONTENT_TYPE, server::conn::http1, service::service_fn, Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use karlsen_consensus_core::api::counters::ProcessingCounters;
+use karlsen_core::{
+    debug, info,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace, warn,
+};
+use karlsen_metrics_core::{
+    openmetrics::{OpenMetricsEncoder, OPENMETRICS_CONTENT_TYPE},
+    MetricsData, MetricsSnapshot,
+};
+use karlsen_rpc_core::api::rpc::RpcApi;
+use karlsen_utils::{networking::NetAddress, triggers::SingleTrigger};
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+use tokio::net::TcpListener;
+
+pub const SERVICE_NAME: &str = "prometheus-service";
+
+/// Port listened to when none is specified
+pub const DEFAULT_PORT: u16 = 9110;
+
+/// Path serving the metrics
+pub const METRICS_PATH: &str = "/metrics";
+
+/// HTTP listener serving the node metrics in the OpenMetrics text format, so that nodes can be scraped by Prometheus.
+///
+/// Metrics are sampled through [`RpcApi::get_metrics`] on every scrape and completed with the consensus pipeline counters.
+pub struct PrometheusService {
+    net_address: NetAddress,
+    rpc: Arc<dyn RpcApi>,
+    processing_counters: Arc<ProcessingCounters>,
+    shutdown: SingleTrigger,
+}
+
+impl PrometheusService {
+    pub fn new(net_address: NetAddress, rpc: Arc<dyn RpcApi>, processing_counters: Arc<ProcessingCounters>) -> Self {
+        Self { net_address, rpc, processing_counters, shutdown: Default::default() }
+    }
+
+    async fn handle(self: Arc<Self>, request: Request<Incoming>) -> Result<Response<Full<Bytes>>, Infallible> {
+        let response = if request.method() == Method::GET && request.uri().path() == METRICS_PATH {
+            Response::builder().header(CONTENT_TYPE, OPENMETRICS_CONTENT_TYPE).body(Full::new(Bytes::from(self.encode().await)))
+        } else {
+            Response::builder().status(StatusCode::NOT_FOUND).body(Full::new(Bytes::new()))
+        };
+        Ok(response.unwrap())
+    }
+
+    async fn encode(&self) -> String {
+        let mut encoder = OpenMetricsEncoder::new();
+
+        match self.rpc.get_metrics(true, true, true, true, true, false).await {
+            Ok(response) => match MetricsData::try_from(response) {
+                // Rates are left to the scraper, so a snapshot of a single sample is enough
+                Ok(data) => encoder.snapshot(&MetricsSnapshot::from((&data, &data))),
+                Err(err) => warn!("[{SERVICE_NAME}] incomplete metrics: {err}"),
+            },
+            Err(err) => warn!("[{SERVICE_NAME}] failed to collect metrics: {err}"),
+        }
+
+        // Consensus pipeline counters not covered by the node metrics
+        let counters = self.processing_counters.snapshot();
+        encoder.counter("node_mergeset_blocks_processed_count", "Processed Mergeset Blocks", counters.mergeset_counts as f64);
+        encoder.counter(
+            "node_chain_blocks_disqualified_count",
+            "Disqualified Chain Blocks",
+            counters.chain_disqualified_counts as f64,
+        );
+
+        encoder.finish()
+    }
+}
+
+impl AsyncService for PrometheusService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        SERVICE_NAME
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", SERVICE_NAME);
+        let shutdown_signal = self.shutdown.listener.clone();
+
+        Box::pin(async move {
+            let listener = TcpListener::bind(SocketAddr::from(self.net_address))
+                .await
+                .map_err(|err| AsyncServiceError::Service(format!("{SERVICE_NAME} failed to bind {}: {err}", self.net_address)))?;
+            info!("Prometheus metrics server listening on http://{}{}", self.net_address, METRICS_PATH);
+
+            tokio::pin!(shutdown_signal);
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown_signal => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, peer)) => {
+                            trace!("[{SERVICE_NAME}] scrape connection from {peer}");
+                            let this = self.clone();
+                            tokio::spawn(async move {
+                                let service = service_fn(move |request| this.clone().handle(request));
+                                if let Err(err) = http1::Builder::new().serve_connection(TokioIo::new(stream), service).await {
+                                    debug!("[{SERVICE_NAME}] connection error: {err}");
+                                }
+                            });
+                        }
+                        Err(err) => warn!("[{SERVICE_NAME}] failed to accept a connection: {err}"),
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", SERVICE_NAME);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", SERVICE_NAME);
+            Ok(())
+        })
+    }
+}
@@ -861,6 +861,15 @@ impl MiningManagerProxy {
         consensus.clone().spawn_blocking(move |c| self.inner.get_block_template(c, &miner_data)).await
     }
 
+    /// Blocking variant of [`Self::get_block_template`], served from the block template cache when it is fresh
+    pub fn get_block_template_blocking(
+        &self,
+        consensus: &dyn ConsensusApi,
+        miner_data: &MinerData,
+    ) -> MiningManagerResult<BlockTemplate> {
+        self.inner.get_block_template(consensus, miner_data)
+    }
+
     /// Returns realtime feerate estimations based on internal mempool state
     pub async fn get_realtime_feerate_estimations(self, virtual_daa_score: u64) -> FeerateEstimations {
         spawn_blocking(move || self.inner.get_realtime_feerate_estimations(virtual_daa_score)).await.unwrap()
@@ -3,8 +3,8 @@ use crate::subscription::context::SubscriptionContext;
 use super::{
     events::EventType,
     subscription::{
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
-        Single,
+        single::{BlockTemplateSubscription, OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        Single, Subscription,
     },
 };
 use std::fmt::{Debug, Display};
@@ -22,6 +22,16 @@ pub trait Notification: Clone + Debug + Display + Send + Sync + 'static {
     fn apply_utxos_changed_subscription(&self, subscription: &UtxosChangedSubscription, context: &SubscriptionContext)
         -> Option<Self>;
 
+    /// Notifications carrying a block template built for the subscription override this method.
+    /// By default, the subscription is applied as an overall one.
+    fn apply_block_template_subscription(
+        &self,
+        subscription: &BlockTemplateSubscription,
+        _context: &SubscriptionContext,
+    ) -> Option<Self> {
+        subscription.active().then(|| self.clone())
+    }
+
     fn apply_subscription(&self, subscription: &dyn Single, context: &SubscriptionContext) -> Option<Self> {
         match subscription.event_type() {
             EventType::VirtualChainChanged => self.apply_virtual_chain_changed_subscription(
@@ -30,6 +40,10 @@ pub trait Notification: Clone + Debug + Display + Send + Sync + 'static {
             ),
             EventType::UtxosChanged => self
                 .apply_utxos_changed_subscription(subscription.as_any().downcast_ref::<UtxosChangedSubscription>().unwrap(), context),
+            EventType::BlockTemplate => self.apply_block_template_subscription(
+                subscription.as_any().downcast_ref::<BlockTemplateSubscription>().unwrap(),
+                context,
+            ),
             _ => self.apply_overall_subscription(subscription.as_any().downcast_ref::<OverallSubscription>().unwrap(), context),
         }
     }
@@ -45,6 +45,7 @@ pub enum Scope {
     VirtualDaaScoreChanged,
     PruningPointUtxoSetOverride,
     NewBlockTemplate,
+    BlockTemplate,
 }
 }
 
@@ -266,3 +267,43 @@ impl Deserializer for NewBlockTemplateScope {
         Ok(Self {})
     }
 }
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct BlockTemplateScope {
+    /// Address the coinbase of the pushed templates pays to
+    pub pay_address: Option<Address>,
+    pub extra_data: Vec<u8>,
+}
+
+impl BlockTemplateScope {
+    pub fn new(pay_address: Address, extra_data: Vec<u8>) -> Self {
+        Self { pay_address: Some(pay_address), extra_data }
+    }
+}
+
+impl std::fmt::Display for BlockTemplateScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.pay_address {
+            Some(ref pay_address) => write!(f, "BlockTemplateScope ({})", pay_address),
+            None => write!(f, "BlockTemplateScope"),
+        }
+    }
+}
+
+impl Serializer for BlockTemplateScope {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Option<Address>, &self.pay_address, writer)?;
+        store!(Vec<u8>, &self.extra_data, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for BlockTemplateScope {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let pay_address = load!(Option<Address>, reader)?;
+        let extra_data = load!(Vec<u8>, reader)?;
+        Ok(Self { pay_address, extra_data })
+    }
+}
@@ -13,6 +13,7 @@ impl ArrayBuilder {
             let event_type = EventType::try_from(i).unwrap();
             let subscription: DynSubscription = match event_type {
                 EventType::VirtualChainChanged => Arc::<single::VirtualChainChangedSubscription>::default(),
+                EventType::BlockTemplate => Arc::<single::BlockTemplateSubscription>::default(),
                 EventType::UtxosChanged => Arc::new(single::UtxosChangedSubscription::with_capacity(
                     single::UtxosChangedState::None,
                     listener_id,
@@ -29,6 +30,7 @@ impl ArrayBuilder {
             let event_type = EventType::try_from(i).unwrap();
             let subscription: CompoundedSubscription = match event_type {
                 EventType::VirtualChainChanged => Box::<compounded::VirtualChainChangedSubscription>::default(),
+                EventType::BlockTemplate => Box::<compounded::BlockTemplateSubscription>::default(),
                 EventType::UtxosChanged => {
                     Box::new(compounded::UtxosChangedSubscription::with_capacity(utxos_changed_capacity.unwrap_or_default()))
                 }
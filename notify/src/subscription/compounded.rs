@@ -1,7 +1,7 @@
 use crate::{
     address::{error::Result, tracker::Counters},
     events::EventType,
-    scope::{Scope, UtxosChangedScope, VirtualChainChangedScope},
+    scope::{BlockTemplateScope, Scope, UtxosChangedScope, VirtualChainChangedScope},
     subscription::{context::SubscriptionContext, Command, Compounded, Mutation, Subscription},
 };
 use itertools::Itertools;
@@ -149,6 +149,54 @@ impl Subscription for VirtualChainChangedSubscription {
     }
 }
 
+/// Counts the BlockTemplate subscribers, keeping the scope of the first one which is the one forwarded to the parent
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct BlockTemplateSubscription {
+    active: usize,
+    scope: BlockTemplateScope,
+}
+
+impl Compounded for BlockTemplateSubscription {
+    fn compound(&mut self, mutation: Mutation, _context: &SubscriptionContext) -> Option<Mutation> {
+        assert_eq!(self.event_type(), mutation.event_type());
+        if let Scope::BlockTemplate(ref scope) = mutation.scope {
+            match mutation.command {
+                Command::Start => {
+                    self.active += 1;
+                    if self.active == 1 {
+                        self.scope = scope.clone();
+                        return Some(mutation);
+                    }
+                }
+                Command::Stop => {
+                    assert!(self.active > 0);
+                    self.active -= 1;
+                    if self.active == 0 {
+                        self.scope = Default::default();
+                        return Some(mutation);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl Subscription for BlockTemplateSubscription {
+    #[inline(always)]
+    fn event_type(&self) -> EventType {
+        EventType::BlockTemplate
+    }
+
+    fn active(&self) -> bool {
+        self.active > 0
+    }
+
+    fn scope(&self, _context: &SubscriptionContext) -> Scope {
+        Scope::BlockTemplate(self.scope.clone())
+    }
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Eq)]
 pub struct UtxosChangedSubscription {
     all: usize,
@@ -310,6 +358,37 @@ mod tests {
         assert!(result.is_err(), "{}: trying to remove when counter is zero must panic", test.name);
     }
 
+    #[test]
+    #[allow(clippy::redundant_clone)]
+    fn test_block_template_compounding() {
+        let a_stock = get_3_addresses(true);
+        let m = |command: Command, index: usize| -> Mutation {
+            let scope = BlockTemplateScope::new(a_stock[index].clone(), vec![index as u8]);
+            Mutation::new(command, Scope::BlockTemplate(scope))
+        };
+        let none = Box::<BlockTemplateSubscription>::default;
+        let test = Test {
+            name: "BlockTemplate 0 to 2 to 0",
+            context: SubscriptionContext::new(),
+            initial_state: none(),
+            steps: vec![
+                Step { name: "add 0", mutation: m(Command::Start, 0), result: Some(m(Command::Start, 0)) },
+                Step { name: "add 1", mutation: m(Command::Start, 1), result: None },
+                Step { name: "remove 0", mutation: m(Command::Stop, 0), result: None },
+                Step { name: "remove 1", mutation: m(Command::Stop, 1), result: Some(m(Command::Stop, 1)) },
+            ],
+            final_state: none(),
+        };
+        test.run();
+
+        // The scope of the first subscriber is reported to the parent
+        let context = SubscriptionContext::new();
+        let mut state = none();
+        state.compound(m(Command::Start, 2), &context);
+        state.compound(m(Command::Start, 1), &context);
+        assert_eq!(state.scope(&context), m(Command::Start, 2).scope);
+    }
+
     #[test]
     #[allow(clippy::redundant_clone)]
     fn test_virtual_chain_changed_compounding() {
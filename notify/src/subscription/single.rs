@@ -3,7 +3,7 @@ use crate::{
     error::Result,
     events::EventType,
     listener::ListenerId,
-    scope::{Scope, UtxosChangedScope, VirtualChainChangedScope},
+    scope::{BlockTemplateScope, Scope, UtxosChangedScope, VirtualChainChangedScope},
     subscription::{
         context::SubscriptionContext, BroadcastingSingle, Command, DynSubscription, Mutation, MutationOutcome, MutationPolicies,
         Single, Subscription, UtxosChangedMutationPolicy,
@@ -172,6 +172,76 @@ impl Subscription for VirtualChainChangedSubscription {
     }
 }
 
+/// Subscription to BlockTemplate notifications
+///
+/// Carries the pay address and extra data the templates of the subscriber are built for.
+#[derive(Eq, PartialEq, Hash, Clone, Debug, Default)]
+pub struct BlockTemplateSubscription {
+    active: bool,
+    pay_address: Option<Address>,
+    extra_data: Vec<u8>,
+}
+
+impl BlockTemplateSubscription {
+    pub fn new(active: bool, pay_address: Option<Address>, extra_data: Vec<u8>) -> Self {
+        Self { active, pay_address, extra_data }
+    }
+
+    pub fn pay_address(&self) -> Option<&Address> {
+        self.pay_address.as_ref()
+    }
+
+    pub fn extra_data(&self) -> &[u8] {
+        &self.extra_data
+    }
+}
+
+impl Single for BlockTemplateSubscription {
+    fn apply_mutation(
+        &self,
+        _: &Arc<dyn Single>,
+        mutation: Mutation,
+        _: MutationPolicies,
+        _: &SubscriptionContext,
+    ) -> Result<MutationOutcome> {
+        assert_eq!(self.event_type(), mutation.event_type());
+        let outcome = if let Scope::BlockTemplate(ref scope) = mutation.scope {
+            match (self.active, mutation.active()) {
+                (false, true) => {
+                    let mutated = Self::new(true, scope.pay_address.clone(), scope.extra_data.clone());
+                    MutationOutcome::with_mutated(Arc::new(mutated), vec![mutation])
+                }
+                (true, true) if self.pay_address != scope.pay_address || self.extra_data != scope.extra_data => {
+                    // The parent is already subscribed so only the data of the templates changes
+                    let mutated = Self::new(true, scope.pay_address.clone(), scope.extra_data.clone());
+                    MutationOutcome::with_mutated(Arc::new(mutated), vec![])
+                }
+                (true, false) => MutationOutcome::with_mutated(Arc::<Self>::default(), vec![mutation]),
+                _ => MutationOutcome::new(),
+            }
+        } else {
+            MutationOutcome::new()
+        };
+        Ok(outcome)
+    }
+}
+
+impl Subscription for BlockTemplateSubscription {
+    #[inline(always)]
+    fn event_type(&self) -> EventType {
+        EventType::BlockTemplate
+    }
+
+    #[inline(always)]
+    fn active(&self) -> bool {
+        self.active
+    }
+
+    fn scope(&self, _context: &SubscriptionContext) -> Scope {
+        BlockTemplateScope { pay_address: self.pay_address.clone(), extra_data: self.extra_data.clone() }.into()
+    }
+}
+
 static UTXOS_CHANGED_SUBSCRIPTIONS: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -924,6 +994,70 @@ mod tests {
         tests.run(&context)
     }
 
+    #[test]
+    fn test_block_template_mutation() {
+        let context = SubscriptionContext::new();
+        let a_stock = get_3_addresses(true);
+
+        let s = |active: bool, index: Option<usize>, extra_data: &[u8]| -> DynSubscription {
+            Arc::new(BlockTemplateSubscription::new(active, index.map(|idx| a_stock[idx].clone()), extra_data.to_vec()))
+        };
+        let m = |command: Command, index: Option<usize>, extra_data: &[u8]| -> Mutation {
+            let scope = BlockTemplateScope { pay_address: index.map(|idx| a_stock[idx].clone()), extra_data: extra_data.to_vec() };
+            Mutation { command, scope: Scope::BlockTemplate(scope) }
+        };
+
+        // Subscriptions
+        let none = || s(false, None, &[]);
+        let paying_0 = || s(true, Some(0), b"miner");
+        let paying_1 = || s(true, Some(1), b"miner");
+
+        // Mutations
+        let start_0 = || m(Command::Start, Some(0), b"miner");
+        let start_1 = || m(Command::Start, Some(1), b"miner");
+        let stop = || m(Command::Stop, None, &[]);
+
+        // Tests
+        let tests = MutationTests::new(vec![
+            MutationTest {
+                name: "BlockTemplateSubscription None to Paying",
+                state: none(),
+                mutation: start_0(),
+                new_state: paying_0(),
+                outcome: MutationOutcome::with_mutated(paying_0(), vec![start_0()]),
+            },
+            MutationTest {
+                name: "BlockTemplateSubscription None to None",
+                state: none(),
+                mutation: stop(),
+                new_state: none(),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "BlockTemplateSubscription Paying to same Paying",
+                state: paying_0(),
+                mutation: start_0(),
+                new_state: paying_0(),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "BlockTemplateSubscription Paying to other Paying",
+                state: paying_0(),
+                mutation: start_1(),
+                new_state: paying_1(),
+                outcome: MutationOutcome::with_mutated(paying_1(), vec![]),
+            },
+            MutationTest {
+                name: "BlockTemplateSubscription Paying to None",
+                state: paying_0(),
+                mutation: stop(),
+                new_state: none(),
+                outcome: MutationOutcome::with_mutated(none(), vec![stop()]),
+            },
+        ]);
+        tests.run(&context)
+    }
+
     #[test]
     fn test_utxos_changed_mutation() {
         let context = SubscriptionContext::new();
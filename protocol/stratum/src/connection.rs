@@ -0,0 +1,87 @@
+use crate::{
+    job::Job,
+    protocol::{to_line, Notification},
+};
+use karlsen_core::karlsend_env::version;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::{collections::VecDeque, net::SocketAddr, sync::Arc};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Number of recent jobs of a connection accepting shares
+pub const MAX_ACTIVE_JOBS: usize = 8;
+
+#[derive(Default)]
+struct ConnectionState {
+    subscribed: bool,
+    worker: Option<String>,
+    next_job_id: u64,
+    jobs: VecDeque<Arc<Job>>,
+}
+
+/// A miner connected to the Stratum server
+pub struct Connection {
+    pub id: u64,
+    pub peer: SocketAddr,
+    /// Unique value written into the coinbase extra data of the jobs of this connection,
+    /// partitioning the search space between miners
+    pub extranonce: u32,
+    sender: UnboundedSender<String>,
+    state: Mutex<ConnectionState>,
+}
+
+impl Connection {
+    pub fn new(id: u64, peer: SocketAddr, sender: UnboundedSender<String>) -> Self {
+        Self { id, peer, extranonce: id as u32, sender, state: Default::default() }
+    }
+
+    /// Coinbase extra data of the jobs of this connection
+    pub fn extra_data(&self) -> Vec<u8> {
+        format!("{}/stratum/{:08x}", version(), self.extranonce).into_bytes()
+    }
+
+    pub fn send<T: Serialize>(&self, message: &T) {
+        // The writer task only stops when the connection closes, so a failed send can be ignored
+        let _ = self.sender.send(to_line(message));
+    }
+
+    pub fn notify(&self, notification: Notification) {
+        self.send(&notification)
+    }
+
+    pub fn subscribe(&self) {
+        self.state.lock().subscribed = true;
+    }
+
+    pub fn is_subscribed(&self) -> bool {
+        self.state.lock().subscribed
+    }
+
+    pub fn authorize(&self, worker: String) {
+        self.state.lock().worker = Some(worker);
+    }
+
+    pub fn worker(&self) -> Option<String> {
+        self.state.lock().worker.clone()
+    }
+
+    /// Reserves the id of the next job of this connection
+    pub fn next_job_id(&self) -> u64 {
+        let mut state = self.state.lock();
+        state.next_job_id += 1;
+        state.next_job_id
+    }
+
+    /// Registers a new job, retiring the oldest one when more than [`MAX_ACTIVE_JOBS`] are active
+    pub fn push_job(&self, job: Arc<Job>) {
+        let mut state = self.state.lock();
+        if state.jobs.len() == MAX_ACTIVE_JOBS {
+            state.jobs.pop_front();
+        }
+        state.jobs.push_back(job);
+    }
+
+    pub fn job(&self, id: u64) -> Option<Arc<Job>> {
+        self.state.lock().jobs.iter().find(|job| job.id == id).cloned()
+    }
+}
@@ -0,0 +1,44 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone)]
+pub enum StratumError {
+    #[error("invalid params: {0}")]
+    InvalidParams(String),
+
+    #[error("unknown method {0}")]
+    UnknownMethod(String),
+
+    #[error("block rejected: {0}")]
+    BlockRejected(String),
+
+    #[error("job not found")]
+    JobNotFound,
+
+    #[error("duplicate share")]
+    DuplicateShare,
+
+    #[error("low difficulty share")]
+    LowDifficultyShare,
+
+    #[error("unauthorized worker")]
+    Unauthorized,
+
+    #[error("not subscribed")]
+    NotSubscribed,
+}
+
+impl StratumError {
+    /// Error code reported to the miner, following the codes used by Stratum v1 pools
+    pub fn code(&self) -> i32 {
+        match self {
+            StratumError::InvalidParams(_) | StratumError::UnknownMethod(_) | StratumError::BlockRejected(_) => 20,
+            StratumError::JobNotFound => 21,
+            StratumError::DuplicateShare => 22,
+            StratumError::LowDifficultyShare => 23,
+            StratumError::Unauthorized => 24,
+            StratumError::NotSubscribed => 25,
+        }
+    }
+}
+
+pub type StratumResult<T> = std::result::Result<T, StratumError>;
@@ -0,0 +1,68 @@
+use crate::error::{StratumError, StratumResult};
+use karlsen_consensus_core::{block::Block, hashing, header::Header, tx::Transaction};
+use karlsen_hashes::pow_hashers::FishHashContext;
+use karlsen_math::Uint256;
+use karlsen_pow::State;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::{collections::HashSet, sync::Arc};
+
+/// Share difficulty 1 maps to a target of 2^224 - 1, i.e. about 2^32 hashes per share
+pub fn difficulty_to_target(difficulty: u64) -> Uint256 {
+    (Uint256::MAX >> 32) / difficulty.max(1)
+}
+
+/// A unit of work handed out to a miner, built from a block template carrying the extranonce of its connection
+pub struct Job {
+    pub id: u64,
+    header: Header,
+    transactions: Arc<Vec<Transaction>>,
+    state: State,
+    /// Nonces already submitted for this job
+    nonces: Mutex<HashSet<u64>>,
+}
+
+impl Job {
+    pub fn new(id: u64, header: Header, transactions: Vec<Transaction>, fish_context: Arc<FishHashContext>) -> Self {
+        let state = State::new(&header, fish_context);
+        Self { id, header, transactions: Arc::new(transactions), state, nonces: Default::default() }
+    }
+
+    /// Params of the `mining.notify` message: the job id, the pre-PoW hash as four little-endian words and the timestamp
+    pub fn notify_params(&self) -> Value {
+        let pre_pow_hash = hashing::header::hash_override_nonce_time(&self.header, 0, 0);
+        json!([self.id.to_string(), pre_pow_hash.to_le_u64(), self.header.timestamp])
+    }
+
+    /// Validates a share against `share_target`.
+    ///
+    /// Returns the solved block when the nonce also meets the target of the block.
+    pub fn submit(&self, nonce: u64, share_target: Uint256) -> StratumResult<Option<Block>> {
+        if !self.nonces.lock().insert(nonce) {
+            return Err(StratumError::DuplicateShare);
+        }
+        let (is_block, pow) = self.state.check_pow(nonce);
+        if is_block {
+            let mut header = self.header.clone();
+            header.nonce = nonce;
+            header.finalize();
+            return Ok(Some(Block::from_arcs(Arc::new(header), self.transactions.clone())));
+        }
+        if pow > share_target {
+            return Err(StratumError::LowDifficultyShare);
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difficulty_to_target() {
+        assert_eq!(difficulty_to_target(1).bits(), 224);
+        assert_eq!(difficulty_to_target(0), difficulty_to_target(1));
+        assert_eq!(difficulty_to_target(1024).bits(), 214);
+    }
+}
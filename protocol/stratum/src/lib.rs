@@ -0,0 +1,7 @@
+pub mod connection;
+pub mod error;
+pub mod job;
+pub mod protocol;
+pub mod service;
+
+pub use service::{StratumService, DEFAULT_DIFFICULTY, DEFAULT_PORT};
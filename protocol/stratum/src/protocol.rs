@@ -0,0 +1,112 @@
+//! Stratum v1 messages: newline delimited JSON-RPC objects exchanged over a raw TCP stream.
+
+use crate::error::{StratumError, StratumResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+pub const SUBSCRIBE: &str = "mining.subscribe";
+pub const EXTRANONCE_SUBSCRIBE: &str = "mining.extranonce.subscribe";
+pub const AUTHORIZE: &str = "mining.authorize";
+pub const SUBMIT: &str = "mining.submit";
+pub const SET_DIFFICULTY: &str = "mining.set_difficulty";
+pub const NOTIFY: &str = "mining.notify";
+
+/// Protocol version reported to subscribing miners
+pub const PROTOCOL_VERSION: &str = "EthereumStratum/1.0.0";
+
+/// A call sent by a miner
+#[derive(Debug, Clone, Deserialize)]
+pub struct Request {
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Vec<Value>,
+}
+
+impl Request {
+    /// Returns the string param at `index`
+    pub fn str_param(&self, index: usize) -> StratumResult<&str> {
+        self.params
+            .get(index)
+            .and_then(Value::as_str)
+            .ok_or_else(|| StratumError::InvalidParams(format!("{} expects a string param at position {}", self.method, index)))
+    }
+}
+
+/// The reply of the server to a [`Request`]
+#[derive(Debug, Clone, Serialize)]
+pub struct Response {
+    pub id: Value,
+    pub result: Value,
+    pub error: Value,
+}
+
+impl Response {
+    pub fn new(id: Value, result: StratumResult<Value>) -> Self {
+        match result {
+            Ok(result) => Self { id, result, error: Value::Null },
+            Err(err) => Self { id, result: Value::Null, error: json!([err.code(), err.to_string(), Value::Null]) },
+        }
+    }
+}
+
+/// A message pushed by the server
+#[derive(Debug, Clone, Serialize)]
+pub struct Notification {
+    pub id: Value,
+    pub method: &'static str,
+    pub params: Value,
+}
+
+impl Notification {
+    pub fn new(method: &'static str, params: Value) -> Self {
+        Self { id: Value::Null, method, params }
+    }
+}
+
+/// Serializes a message as a single line of the stream
+pub fn to_line<T: Serialize>(message: &T) -> String {
+    let mut line = serde_json::to_string(message).unwrap();
+    line.push('\n');
+    line
+}
+
+/// Parses a nonce submitted as a hex string, with or without a `0x` prefix
+pub fn parse_nonce(nonce: &str) -> StratumResult<u64> {
+    let digits = nonce.strip_prefix("0x").unwrap_or(nonce);
+    u64::from_str_radix(digits, 16).map_err(|_| StratumError::InvalidParams(format!("malformed nonce {nonce}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_params() {
+        let request: Request =
+            serde_json::from_str(r#"{"id":4,"method":"mining.submit","params":["worker","12","0x00000000000000ff"]}"#).unwrap();
+        assert_eq!(request.method, SUBMIT);
+        assert_eq!(request.str_param(1).unwrap(), "12");
+        assert!(matches!(request.str_param(3), Err(StratumError::InvalidParams(_))));
+        assert_eq!(parse_nonce(request.str_param(2).unwrap()).unwrap(), 0xff);
+        assert_eq!(parse_nonce("ff").unwrap(), 0xff);
+        assert!(parse_nonce("0xzz").is_err());
+
+        let request: Request = serde_json::from_str(r#"{"method":"mining.subscribe"}"#).unwrap();
+        assert!(request.id.is_null() && request.params.is_empty());
+    }
+
+    #[test]
+    fn test_response_lines() {
+        assert_eq!(to_line(&Response::new(json!(1), Ok(json!(true)))), "{\"id\":1,\"result\":true,\"error\":null}\n");
+        assert_eq!(
+            to_line(&Response::new(json!(2), Err(StratumError::JobNotFound))),
+            "{\"id\":2,\"result\":null,\"error\":[21,\"job not found\",null]}\n"
+        );
+        assert_eq!(
+            to_line(&Notification::new(SET_DIFFICULTY, json!([4]))),
+            "{\"id\":null,\"method\":\"mining.set_difficulty\",\"params\":[4]}\n"
+        );
+    }
+}
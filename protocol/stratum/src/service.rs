@@ -0,0 +1,289 @@
+use crate::{
+    connection::Connection,
+    error::{StratumError, StratumResult},
+    job::{difficulty_to_target, Job},
+    protocol::{self, parse_nonce, Notification, Request, Response, PROTOCOL_VERSION},
+};
+use karlsen_addresses::Address;
+use karlsen_consensus_core::{coinbase::MinerData, network::NetworkType};
+use karlsen_core::{
+    debug, info,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace, warn,
+};
+use karlsen_hashes::pow_hashers::FishHashContext;
+use karlsen_math::Uint256;
+use karlsen_notify::{
+    connection::ChannelType,
+    scope::{NewBlockTemplateScope, Scope},
+};
+use karlsen_p2p_flows::flow_context::FlowContext;
+use karlsen_rpc_core::{
+    api::rpc::RpcApi,
+    notify::{channel::NotificationChannel, connection::ChannelConnection},
+    Notification as RpcNotification,
+};
+use karlsen_txscript::pay_to_address_script;
+use karlsen_utils::{networking::NetAddress, triggers::SingleTrigger};
+use parking_lot::Mutex;
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+};
+
+pub const SERVICE_NAME: &str = "stratum-service";
+
+/// Port listened to when none is specified
+pub const DEFAULT_PORT: u16 = 5555;
+
+/// Share difficulty used when none is specified
+pub const DEFAULT_DIFFICULTY: u64 = 1;
+
+/// Stratum v1 server letting miners work directly against the node.
+///
+/// Every connection gets its own extranonce written into the coinbase extra data, so miners never share a search space.
+/// New jobs are pushed on each `NewBlockTemplate` notification, shares are validated locally and solved blocks are
+/// submitted through the flow context, which adds them to the DAG, updates the mining manager and relays them to peers.
+pub struct StratumService {
+    net_address: NetAddress,
+    pay_address: Address,
+    difficulty: u64,
+    share_target: Uint256,
+    flow_context: Arc<FlowContext>,
+    rpc: Arc<dyn RpcApi>,
+    fish_context: Arc<FishHashContext>,
+    connections: Mutex<HashMap<u64, Arc<Connection>>>,
+    next_connection_id: AtomicU64,
+    shutdown: SingleTrigger,
+}
+
+impl StratumService {
+    pub fn new(
+        net_address: NetAddress,
+        pay_address: Address,
+        difficulty: u64,
+        flow_context: Arc<FlowContext>,
+        rpc: Arc<dyn RpcApi>,
+        fish_context: Arc<FishHashContext>,
+    ) -> Self {
+        Self {
+            net_address,
+            pay_address,
+            difficulty,
+            share_target: difficulty_to_target(difficulty),
+            flow_context,
+            rpc,
+            fish_context,
+            connections: Default::default(),
+            next_connection_id: Default::default(),
+            shutdown: Default::default(),
+        }
+    }
+
+    async fn handle_connection(self: Arc<Self>, stream: TcpStream, peer: SocketAddr) {
+        let id = self.next_connection_id.fetch_add(1, Ordering::SeqCst);
+        let (reader, mut writer) = stream.into_split();
+        let (sender, mut receiver) = mpsc::unbounded_channel::<String>();
+        let connection = Arc::new(Connection::new(id, peer, sender));
+        self.connections.lock().insert(id, connection.clone());
+        debug!("[{SERVICE_NAME}] miner {peer} connected with extranonce {:08x}", connection.extranonce);
+
+        tokio::spawn(async move {
+            while let Some(line) = receiver.recv().await {
+                if writer.write_all(line.as_bytes()).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let shutdown_signal = self.shutdown.listener.clone();
+        tokio::pin!(shutdown_signal);
+        let mut lines = BufReader::new(reader).lines();
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_signal => break,
+                line = lines.next_line() => match line {
+                    Ok(Some(line)) if line.trim().is_empty() => continue,
+                    Ok(Some(line)) => match serde_json::from_str::<Request>(&line) {
+                        Ok(request) => self.handle_request(&connection, request).await,
+                        Err(err) => {
+                            debug!("[{SERVICE_NAME}] malformed message from {peer}: {err}");
+                            break;
+                        }
+                    },
+                    Ok(None) => break,
+                    Err(err) => {
+                        debug!("[{SERVICE_NAME}] connection error with {peer}: {err}");
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Dropping the last connection reference closes the channel and ends the writer task
+        self.connections.lock().remove(&id);
+        debug!("[{SERVICE_NAME}] miner {peer} disconnected");
+    }
+
+    async fn handle_request(&self, connection: &Connection, request: Request) {
+        trace!("[{SERVICE_NAME}] {} from {}", request.method, connection.peer);
+        let result = match request.method.as_str() {
+            protocol::SUBSCRIBE => {
+                connection.subscribe();
+                Ok(json!([true, PROTOCOL_VERSION]))
+            }
+            protocol::EXTRANONCE_SUBSCRIBE => Ok(json!(true)),
+            protocol::AUTHORIZE => self.authorize(connection, &request).map(|_| json!(true)),
+            protocol::SUBMIT => self.submit(connection, &request).await.map(|_| json!(true)),
+            method => Err(StratumError::UnknownMethod(method.to_string())),
+        };
+        let authorized = request.method == protocol::AUTHORIZE && result.is_ok();
+        connection.send(&Response::new(request.id, result));
+
+        if authorized {
+            connection.notify(Notification::new(protocol::SET_DIFFICULTY, json!([self.difficulty])));
+            self.send_job(connection).await;
+        }
+    }
+
+    fn authorize(&self, connection: &Connection, request: &Request) -> StratumResult<()> {
+        if !connection.is_subscribed() {
+            return Err(StratumError::NotSubscribed);
+        }
+        let worker = request.str_param(0)?;
+        info!("Stratum worker {} authorized from {}", worker, connection.peer);
+        connection.authorize(worker.to_string());
+        Ok(())
+    }
+
+    async fn submit(&self, connection: &Connection, request: &Request) -> StratumResult<()> {
+        let worker = connection.worker().ok_or(StratumError::Unauthorized)?;
+        let job_id = request.str_param(1)?;
+        let job_id = job_id.parse::<u64>().map_err(|_| StratumError::InvalidParams(format!("malformed job id {job_id}")))?;
+        let nonce = parse_nonce(request.str_param(2)?)?;
+        let job = connection.job(job_id).ok_or(StratumError::JobNotFound)?;
+
+        let Some(block) = job.submit(nonce, self.share_target)? else {
+            return Ok(());
+        };
+        let hash = block.hash();
+        let session = self.flow_context.consensus().unguarded_session();
+        match self.flow_context.submit_rpc_block(&session, block).await {
+            Ok(_) => {
+                info!("Stratum worker {} found block {}", worker, hash);
+                Ok(())
+            }
+            Err(err) => {
+                warn!("The block {} submitted by Stratum worker {} was rejected: {}", hash, worker, err);
+                Err(StratumError::BlockRejected(err.to_string()))
+            }
+        }
+    }
+
+    /// Builds a job for `connection` from the current block template and notifies the miner
+    async fn send_job(&self, connection: &Connection) {
+        let config = &self.flow_context.config;
+        if *config.net == NetworkType::Mainnet && !config.enable_mainnet_mining {
+            debug!("[{SERVICE_NAME}] mining on mainnet is not supported for initial Rust versions");
+            return;
+        }
+
+        let session = self.flow_context.consensus().unguarded_session();
+        if !config.enable_unsynced_mining && !self.flow_context.should_mine(&session).await {
+            debug!("[{SERVICE_NAME}] no job sent to {} since the node is not synced", connection.peer);
+            return;
+        }
+
+        let miner_data = MinerData::new(pay_to_address_script(&self.pay_address), connection.extra_data());
+        match self.flow_context.mining_manager().clone().get_block_template(&session, miner_data).await {
+            Ok(block_template) => {
+                let block = block_template.block;
+                let job = Arc::new(Job::new(connection.next_job_id(), block.header, block.transactions, self.fish_context.clone()));
+                connection.push_job(job.clone());
+                connection.notify(Notification::new(protocol::NOTIFY, job.notify_params()));
+            }
+            Err(err) => warn!("[{SERVICE_NAME}] failed to build a block template: {err}"),
+        }
+    }
+
+    /// Pushes a new job to every authorized miner
+    async fn broadcast_jobs(&self) {
+        let connections =
+            self.connections.lock().values().filter(|connection| connection.worker().is_some()).cloned().collect::<Vec<_>>();
+        for connection in connections {
+            self.send_job(&connection).await;
+        }
+    }
+}
+
+impl AsyncService for StratumService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        SERVICE_NAME
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", SERVICE_NAME);
+        let shutdown_signal = self.shutdown.listener.clone();
+
+        Box::pin(async move {
+            let listener = TcpListener::bind(SocketAddr::from(self.net_address))
+                .await
+                .map_err(|err| AsyncServiceError::Service(format!("{SERVICE_NAME} failed to bind {}: {err}", self.net_address)))?;
+
+            // Get a new job for all miners whenever the block template changes
+            let channel = NotificationChannel::default();
+            let listener_id =
+                self.rpc.register_new_listener(ChannelConnection::new(SERVICE_NAME, channel.sender(), ChannelType::Closable));
+            self.rpc
+                .start_notify(listener_id, Scope::NewBlockTemplate(NewBlockTemplateScope {}))
+                .await
+                .map_err(|err| AsyncServiceError::Service(format!("{SERVICE_NAME} failed to subscribe to block templates: {err}")))?;
+            let notifications = channel.receiver();
+            info!("Stratum server listening on {} and paying to {}", self.net_address, self.pay_address);
+
+            tokio::pin!(shutdown_signal);
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown_signal => break,
+                    notification = notifications.recv() => match notification {
+                        Ok(RpcNotification::NewBlockTemplate(_)) => self.broadcast_jobs().await,
+                        Ok(_) => {}
+                        Err(_) => break,
+                    },
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, peer)) => {
+                            tokio::spawn(self.clone().handle_connection(stream, peer));
+                        }
+                        Err(err) => warn!("[{SERVICE_NAME}] failed to accept a connection: {err}"),
+                    }
+                }
+            }
+
+            // The notifier may already be stopped during shutdown, so the error is ignored
+            let _ = self.rpc.unregister_listener(listener_id).await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", SERVICE_NAME);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", SERVICE_NAME);
+            Ok(())
+        })
+    }
+}
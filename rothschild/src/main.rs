@@ -138,6 +138,7 @@ async fn new_rpc_client(subscription_context: &SubscriptionContext, address: &st
         false,
         Some(500_000),
         Default::default(),
+        None,
     )
     .await
     .unwrap()
@@ -173,6 +174,7 @@ async fn main() {
         false,
         Some(500_000),
         Default::default(),
+        None,
     )
     .await
     .expect("Critical error: failed to connect to the RPC server.");
@@ -0,0 +1,277 @@
+//!
+//! Role based access control of the RPC methods.
+//!
+//! Clients present a token mapped to a [`RpcRole`] allowing a set of [`RpcApiOps`].
+//! Clients presenting no token get the anonymous role of the [`RpcAccessPolicy`], if any.
+//!
+
+use crate::{api::ops::RpcApiOps, RpcError, RpcResult};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    sync::Arc,
+};
+
+/// Ops available to any connection, whatever its role
+const CONNECTION_OPS: &[RpcApiOps] =
+    &[RpcApiOps::NoOp, RpcApiOps::Connect, RpcApiOps::Disconnect, RpcApiOps::Authenticate, RpcApiOps::Ping];
+
+/// Ops building and submitting blocks, granted to the miner role
+const MINER_OPS: &[RpcApiOps] = &[RpcApiOps::GetBlockTemplate, RpcApiOps::SubmitBlock, RpcApiOps::NotifyBlockTemplate];
+
+/// Ops changing the mempool, the peers or the state of the node, reserved to the admin role
+const ADMIN_OPS: &[RpcApiOps] = &[
+    RpcApiOps::SubmitTransaction,
+    RpcApiOps::SubmitTransactionReplacement,
+    RpcApiOps::AddPeer,
+    RpcApiOps::Ban,
+    RpcApiOps::Unban,
+    RpcApiOps::ResolveFinalityConflict,
+    RpcApiOps::Shutdown,
+    RpcApiOps::CreateBackup,
+];
+
+/// A named set of RPC methods a connection is allowed to call
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RpcRole {
+    name: String,
+    allowed: HashSet<RpcApiOps>,
+}
+
+impl RpcRole {
+    /// Read-only access
+    pub const PUBLIC: &'static str = "public";
+    /// Read-only access along with building and submitting blocks
+    pub const MINER: &'static str = "miner";
+    /// Unrestricted access
+    pub const ADMIN: &'static str = "admin";
+
+    pub fn new(name: String, allowed: HashSet<RpcApiOps>) -> Self {
+        Self { name, allowed }
+    }
+
+    pub fn public() -> Self {
+        let allowed = RpcApiOps::into_iter().filter(|op| !MINER_OPS.contains(op) && !ADMIN_OPS.contains(op)).collect();
+        Self::new(Self::PUBLIC.to_owned(), allowed)
+    }
+
+    pub fn miner() -> Self {
+        let allowed = RpcApiOps::into_iter().filter(|op| !ADMIN_OPS.contains(op)).collect();
+        Self::new(Self::MINER.to_owned(), allowed)
+    }
+
+    pub fn admin() -> Self {
+        Self::new(Self::ADMIN.to_owned(), RpcApiOps::into_iter().collect())
+    }
+
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            Self::PUBLIC => Some(Self::public()),
+            Self::MINER => Some(Self::miner()),
+            Self::ADMIN => Some(Self::admin()),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn allows(&self, op: RpcApiOps) -> bool {
+        CONNECTION_OPS.contains(&op) || self.allowed.contains(&op)
+    }
+
+    /// Returns an error if the role does not allow `op`
+    pub fn authorize(&self, op: RpcApiOps) -> RpcResult<()> {
+        match self.allows(op) {
+            true => Ok(()),
+            false => Err(RpcError::AccessDenied(op.as_str().to_owned(), self.name.clone())),
+        }
+    }
+
+    /// Parses a role definition of the form `NAME:ITEM[,ITEM...]`, resolving role names against the
+    /// built-in roles and `roles`.
+    ///
+    /// Items are applied in order: a role name adds all the methods of the role, a method name
+    /// (optionally prefixed by `+`) allows the method and a method name prefixed by `-` denies it.
+    pub fn parse(definition: &str, roles: &HashMap<String, RpcRole>) -> RpcResult<Self> {
+        let invalid = |reason: String| RpcError::General(format!("invalid RPC role definition `{definition}`: {reason}"));
+        let (name, items) = definition.split_once(':').ok_or_else(|| invalid("expected NAME:ITEM[,ITEM...]".to_owned()))?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(invalid("missing role name".to_owned()));
+        }
+        let mut allowed = HashSet::new();
+        for item in items.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            if let Some(op) = item.strip_prefix('-') {
+                allowed.remove(&Self::parse_op(op).ok_or_else(|| invalid(format!("unknown method `{op}`")))?);
+            } else if let Some(role) = roles.get(item).cloned().or_else(|| Self::builtin(item)) {
+                allowed.extend(role.allowed);
+            } else {
+                let op = item.strip_prefix('+').unwrap_or(item);
+                allowed.insert(Self::parse_op(op).ok_or_else(|| invalid(format!("unknown role or method `{op}`")))?);
+            }
+        }
+        Ok(Self::new(name.to_owned(), allowed))
+    }
+
+    fn parse_op(name: &str) -> Option<RpcApiOps> {
+        RpcApiOps::into_iter().find(|op| op.as_str().eq_ignore_ascii_case(name))
+    }
+}
+
+/// Maps the tokens presented by RPC clients to their role
+#[derive(Clone, Debug)]
+pub struct RpcAccessPolicy {
+    tokens: HashMap<String, Arc<RpcRole>>,
+    anonymous: Option<Arc<RpcRole>>,
+}
+
+impl RpcAccessPolicy {
+    pub fn new(anonymous: Option<RpcRole>) -> Self {
+        Self { tokens: HashMap::new(), anonymous: anonymous.map(Arc::new) }
+    }
+
+    /// A policy granting the admin role to every client, the behavior of a node without configured tokens
+    pub fn unrestricted() -> Self {
+        Self::new(Some(RpcRole::admin()))
+    }
+
+    /// Builds the policy from the role definitions (see [`RpcRole::parse`]), the `ROLE:TOKEN` pairs and
+    /// the role of the clients presenting no token.
+    ///
+    /// Without any token, every client is granted the admin role unless `anonymous_role` says otherwise.
+    /// With some tokens, the anonymous role defaults to public. An anonymous role `none` rejects
+    /// clients presenting no token.
+    pub fn from_config(role_definitions: &[String], tokens: &[String], anonymous_role: Option<&str>) -> RpcResult<Self> {
+        let mut roles = HashMap::<String, RpcRole>::new();
+        for definition in role_definitions {
+            let role = RpcRole::parse(definition, &roles)?;
+            roles.insert(role.name.clone(), role);
+        }
+        let resolve = |name: &str| {
+            roles
+                .get(name)
+                .cloned()
+                .or_else(|| RpcRole::builtin(name))
+                .ok_or_else(|| RpcError::General(format!("unknown RPC role `{name}`")))
+        };
+
+        let default_anonymous_role = if tokens.is_empty() { RpcRole::ADMIN } else { RpcRole::PUBLIC };
+        let anonymous = match anonymous_role.unwrap_or(default_anonymous_role) {
+            "none" => None,
+            name => Some(resolve(name)?),
+        };
+
+        let mut policy = Self::new(anonymous);
+        for token in tokens {
+            let (role, token) = token
+                .split_once(':')
+                .filter(|(_, token)| !token.is_empty())
+                .ok_or_else(|| RpcError::General(format!("invalid RPC token `{token}`: expected ROLE:TOKEN")))?;
+            policy.tokens.insert(token.to_owned(), Arc::new(resolve(role)?));
+        }
+        Ok(policy)
+    }
+
+    /// Returns the role of a client presenting `token`
+    pub fn authenticate(&self, token: Option<&str>) -> RpcResult<Arc<RpcRole>> {
+        match token {
+            Some(token) => self.tokens.get(token).cloned().ok_or(RpcError::InvalidAuthToken),
+            None => self.anonymous.clone().ok_or(RpcError::Unauthenticated),
+        }
+    }
+
+    /// The role of the clients presenting no token, if they are accepted
+    pub fn anonymous(&self) -> Option<Arc<RpcRole>> {
+        self.anonymous.clone()
+    }
+}
+
+impl Default for RpcAccessPolicy {
+    fn default() -> Self {
+        Self::unrestricted()
+    }
+}
+
+impl FromStr for RpcRole {
+    type Err = RpcError;
+
+    fn from_str(definition: &str) -> Result<Self, Self::Err> {
+        Self::parse(definition, &HashMap::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_roles() {
+        let public = RpcRole::public();
+        assert!(public.allows(RpcApiOps::GetInfo));
+        assert!(public.allows(RpcApiOps::NotifyUtxosChanged));
+        assert!(!public.allows(RpcApiOps::GetBlockTemplate));
+        assert!(!public.allows(RpcApiOps::SubmitTransaction));
+
+        let miner = RpcRole::miner();
+        assert!(miner.allows(RpcApiOps::GetBlockTemplate));
+        assert!(miner.allows(RpcApiOps::SubmitBlock));
+        assert!(!miner.allows(RpcApiOps::AddPeer));
+
+        let admin = RpcRole::admin();
+        assert!(admin.allows(RpcApiOps::Shutdown));
+        assert!(admin.allows(RpcApiOps::Ban));
+
+        // Connection control is always allowed
+        let none = RpcRole::new("none".to_owned(), HashSet::new());
+        assert!(none.allows(RpcApiOps::Ping));
+        assert!(none.allows(RpcApiOps::Authenticate));
+        assert!(matches!(none.authorize(RpcApiOps::GetInfo), Err(RpcError::AccessDenied(..))));
+    }
+
+    #[test]
+    fn test_role_definitions() {
+        let wallet: RpcRole = "wallet:public,+SubmitTransaction,-GetMempoolEntries".parse().unwrap();
+        assert_eq!(wallet.name(), "wallet");
+        assert!(wallet.allows(RpcApiOps::GetUtxosByAddresses));
+        assert!(wallet.allows(RpcApiOps::SubmitTransaction));
+        assert!(!wallet.allows(RpcApiOps::GetMempoolEntries));
+        assert!(!wallet.allows(RpcApiOps::SubmitBlock));
+
+        let feed: RpcRole = "feed:getinfo,NotifyBlockAdded".parse().unwrap();
+        assert!(feed.allows(RpcApiOps::GetInfo));
+        assert!(feed.allows(RpcApiOps::NotifyBlockAdded));
+        assert!(!feed.allows(RpcApiOps::GetBlock));
+
+        assert!("wallet".parse::<RpcRole>().is_err());
+        assert!(":public".parse::<RpcRole>().is_err());
+        assert!("wallet:public,-Unknown".parse::<RpcRole>().is_err());
+        assert!("wallet:unknown".parse::<RpcRole>().is_err());
+    }
+
+    #[test]
+    fn test_access_policy() {
+        // Without tokens, anonymous clients keep an unrestricted access
+        let policy = RpcAccessPolicy::from_config(&[], &[], None).unwrap();
+        assert_eq!(policy.authenticate(None).unwrap().name(), RpcRole::ADMIN);
+        assert!(matches!(policy.authenticate(Some("secret")), Err(RpcError::InvalidAuthToken)));
+
+        // With tokens, anonymous clients default to the public role
+        let roles = vec!["pool:miner,SubmitTransaction".to_owned()];
+        let tokens = vec!["admin:s3cr3t".to_owned(), "pool:p00l".to_owned()];
+        let policy = RpcAccessPolicy::from_config(&roles, &tokens, None).unwrap();
+        assert_eq!(policy.authenticate(None).unwrap().name(), RpcRole::PUBLIC);
+        assert_eq!(policy.authenticate(Some("s3cr3t")).unwrap().name(), RpcRole::ADMIN);
+        let pool = policy.authenticate(Some("p00l")).unwrap();
+        assert!(pool.allows(RpcApiOps::SubmitBlock) && pool.allows(RpcApiOps::SubmitTransaction));
+        assert!(matches!(policy.authenticate(Some("p00l ")), Err(RpcError::InvalidAuthToken)));
+
+        let policy = RpcAccessPolicy::from_config(&roles, &tokens, Some("none")).unwrap();
+        assert!(matches!(policy.authenticate(None), Err(RpcError::Unauthenticated)));
+
+        assert!(RpcAccessPolicy::from_config(&[], &["unknown:token".to_owned()], None).is_err());
+        assert!(RpcAccessPolicy::from_config(&[], &["admin:".to_owned()], None).is_err());
+        assert!(RpcAccessPolicy::from_config(&[], &[], Some("unknown")).is_err());
+    }
+}
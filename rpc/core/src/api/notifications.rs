@@ -9,7 +9,7 @@ use karlsen_notify::{
     notification::{full_featured, Notification as NotificationTrait},
     subscription::{
         context::SubscriptionContext,
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        single::{BlockTemplateSubscription, OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
         Subscription,
     },
 };
@@ -48,6 +48,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "BlockTemplate notification")]
+    BlockTemplate(BlockTemplateNotification),
 }
 }
 
@@ -64,6 +67,7 @@ impl Notification {
             Notification::VirtualDaaScoreChanged(v) => to_value(&v),
             Notification::SinkBlueScoreChanged(v) => to_value(&v),
             Notification::VirtualChainChanged(v) => to_value(&v),
+            Notification::BlockTemplate(v) => to_value(&v),
         }
     }
 }
@@ -114,6 +118,22 @@ impl NotificationTrait for Notification {
         }
     }
 
+    fn apply_block_template_subscription(
+        &self,
+        subscription: &BlockTemplateSubscription,
+        _context: &SubscriptionContext,
+    ) -> Option<Self> {
+        match subscription.active() {
+            true => {
+                let Self::BlockTemplate(notification) = self else {
+                    return None;
+                };
+                notification.apply_block_template_subscription(subscription).map(Self::BlockTemplate)
+            }
+            false => None,
+        }
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
@@ -159,6 +179,10 @@ impl Serializer for Notification {
                 store!(u16, &8, writer)?;
                 serialize!(NewBlockTemplateNotification, notification, writer)?;
             }
+            Notification::BlockTemplate(notification) => {
+                store!(u16, &9, writer)?;
+                serialize!(BlockTemplateNotification, notification, writer)?;
+            }
         }
         Ok(())
     }
@@ -204,6 +228,10 @@ impl Deserializer for Notification {
                 let notification = deserialize!(NewBlockTemplateNotification, reader)?;
                 Ok(Notification::NewBlockTemplate(notification))
             }
+            9 => {
+                let notification = deserialize!(BlockTemplateNotification, reader)?;
+                Ok(Notification::BlockTemplate(notification))
+            }
             _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid variant")),
         }
     }
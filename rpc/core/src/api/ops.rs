@@ -28,6 +28,9 @@ pub enum RpcApiOps {
     Subscribe = 3,
     Unsubscribe = 4,
 
+    // wRPC authentication, granting the connection the role of a token
+    Authenticate = 5,
+
     // ~~~
 
     // Subscription commands for starting/stopping notifications
@@ -40,6 +43,7 @@ pub enum RpcApiOps {
     NotifyVirtualDaaScoreChanged = 16,
     NotifyVirtualChainChanged = 17,
     NotifySinkBlueScoreChanged = 18,
+    NotifyBlockTemplate = 19,
 
     // Notification ops required by wRPC
 
@@ -54,6 +58,7 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification = 66,
     PruningPointUtxoSetOverrideNotification = 67,
     NewBlockTemplateNotification = 68,
+    BlockTemplateNotification = 69,
 
     // RPC methods
     /// Ping the node to check if connection is alive
@@ -138,6 +143,14 @@ pub enum RpcApiOps {
     GetCurrentBlockColor = 149,
     /// Get UTXO Return Addresses
     GetUtxoReturnAddress = 150,
+    /// Get a transaction by id from the transaction index
+    GetTransaction = 151,
+    /// Get a set of transactions by ids from the transaction index
+    GetTransactionsByIds = 152,
+    /// Get a page of the history of an address from the address history index
+    GetAddressHistory = 153,
+    /// Create a backup of the node databases
+    CreateBackup = 154,
 }
 
 impl RpcApiOps {
@@ -153,12 +166,31 @@ impl RpcApiOps {
                 | RpcApiOps::NotifyFinalityConflictResolved
                 | RpcApiOps::NotifySinkBlueScoreChanged
                 | RpcApiOps::NotifyVirtualDaaScoreChanged
+                | RpcApiOps::NotifyBlockTemplate
                 | RpcApiOps::Subscribe
                 | RpcApiOps::Unsubscribe
         )
     }
 }
 
+impl RpcApiOps {
+    /// The subscription op starting the notifications of an event type
+    pub fn subscription(event: EventType) -> Self {
+        match event {
+            EventType::BlockAdded => RpcApiOps::NotifyBlockAdded,
+            EventType::VirtualChainChanged => RpcApiOps::NotifyVirtualChainChanged,
+            EventType::FinalityConflict => RpcApiOps::NotifyFinalityConflict,
+            EventType::FinalityConflictResolved => RpcApiOps::NotifyFinalityConflictResolved,
+            EventType::UtxosChanged => RpcApiOps::NotifyUtxosChanged,
+            EventType::SinkBlueScoreChanged => RpcApiOps::NotifySinkBlueScoreChanged,
+            EventType::VirtualDaaScoreChanged => RpcApiOps::NotifyVirtualDaaScoreChanged,
+            EventType::PruningPointUtxoSetOverride => RpcApiOps::NotifyPruningPointUtxoSetOverride,
+            EventType::NewBlockTemplate => RpcApiOps::NotifyNewBlockTemplate,
+            EventType::BlockTemplate => RpcApiOps::NotifyBlockTemplate,
+        }
+    }
+}
+
 impl From<RpcApiOps> for u32 {
     fn from(item: RpcApiOps) -> Self {
         item as u32
@@ -179,6 +211,7 @@ impl From<EventType> for RpcApiOps {
             EventType::VirtualDaaScoreChanged => RpcApiOps::VirtualDaaScoreChangedNotification,
             EventType::PruningPointUtxoSetOverride => RpcApiOps::PruningPointUtxoSetOverrideNotification,
             EventType::NewBlockTemplate => RpcApiOps::NewBlockTemplateNotification,
+            EventType::BlockTemplate => RpcApiOps::BlockTemplateNotification,
         }
     }
 }
@@ -0,0 +1,323 @@
+//!
+//! Rate limiting of the RPC clients.
+//!
+//! Every request has a cost in units (see [`RequestCost`]), roughly proportional to the work it requires
+//! from the node. Clients sharing an IP address draw these units from a common token bucket
+//! ([`RpcRateLimiter`]) refilled at a configurable rate.
+//!
+
+use crate::{model::message::*, RpcError, RpcResult};
+use karlsen_notify::scope::Scope;
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, Instant},
+};
+
+/// Cost of the requests of a fixed size
+pub const UNIT_COST: u32 = 1;
+
+/// Cost of the requests walking the DAG from an arbitrary block or scanning the whole mempool
+pub const SCAN_COST: u32 = 100;
+
+/// Number of headers or blocks covered by a unit of cost
+const ITEMS_PER_UNIT: u64 = 100;
+
+/// The cost in units of an RPC request
+pub trait RequestCost {
+    fn cost(&self) -> u32 {
+        UNIT_COST
+    }
+}
+
+macro_rules! unit_cost {
+    ($($request:ty),* $(,)?) => {
+        $(impl RequestCost for $request {})*
+    };
+}
+
+unit_cost!(
+    PingRequest,
+    AddPeerRequest,
+    BanRequest,
+    UnbanRequest,
+    GetBalanceByAddressRequest,
+    GetBlockRequest,
+    GetBlockCountRequest,
+    GetBlockDagInfoRequest,
+    GetBlockTemplateRequest,
+    GetCurrentBlockColorRequest,
+    GetCoinSupplyRequest,
+    GetConnectedPeerInfoRequest,
+    GetConnectionsRequest,
+    GetCurrentNetworkRequest,
+    GetFeeEstimateRequest,
+    GetFeeEstimateExperimentalRequest,
+    GetInfoRequest,
+    GetMempoolEntryRequest,
+    GetMetricsRequest,
+    GetPeerAddressesRequest,
+    GetServerInfoRequest,
+    GetSinkRequest,
+    GetSinkBlueScoreRequest,
+    GetSubnetworkRequest,
+    GetSyncStatusRequest,
+    GetSystemInfoRequest,
+    GetTransactionRequest,
+    GetUtxoReturnAddressRequest,
+    ResolveFinalityConflictRequest,
+    ShutdownRequest,
+    SubmitBlockRequest,
+    SubmitTransactionRequest,
+    SubmitTransactionReplacementRequest,
+);
+
+fn count_cost(count: usize) -> u32 {
+    u32::try_from(count).unwrap_or(u32::MAX).max(UNIT_COST)
+}
+
+impl RequestCost for GetUtxosByAddressesRequest {
+    fn cost(&self) -> u32 {
+        count_cost(self.addresses.len())
+    }
+}
+
+impl RequestCost for GetBalancesByAddressesRequest {
+    fn cost(&self) -> u32 {
+        count_cost(self.addresses.len())
+    }
+}
+
+impl RequestCost for GetMempoolEntriesByAddressesRequest {
+    fn cost(&self) -> u32 {
+        count_cost(self.addresses.len())
+    }
+}
+
+impl RequestCost for GetTransactionsByIdsRequest {
+    fn cost(&self) -> u32 {
+        count_cost(self.transaction_ids.len())
+    }
+}
+
+impl RequestCost for GetDaaScoreTimestampEstimateRequest {
+    fn cost(&self) -> u32 {
+        count_cost(self.daa_scores.len())
+    }
+}
+
+impl RequestCost for GetAddressHistoryRequest {
+    fn cost(&self) -> u32 {
+        count_cost(self.limit as usize / ITEMS_PER_UNIT as usize)
+    }
+}
+
+impl RequestCost for GetHeadersRequest {
+    fn cost(&self) -> u32 {
+        count_cost((self.limit / ITEMS_PER_UNIT) as usize)
+    }
+}
+
+impl RequestCost for EstimateNetworkHashesPerSecondRequest {
+    fn cost(&self) -> u32 {
+        count_cost((self.window_size as u64 / ITEMS_PER_UNIT) as usize)
+    }
+}
+
+impl RequestCost for GetBlocksRequest {
+    fn cost(&self) -> u32 {
+        // The range of blocks ends at the sink, so is only known once walked
+        match (self.include_blocks, self.include_transactions) {
+            (true, true) => 4 * SCAN_COST,
+            (true, false) => 2 * SCAN_COST,
+            (false, _) => SCAN_COST,
+        }
+    }
+}
+
+impl RequestCost for GetVirtualChainFromBlockRequest {
+    fn cost(&self) -> u32 {
+        match self.include_accepted_transaction_ids {
+            true => 2 * SCAN_COST,
+            false => SCAN_COST,
+        }
+    }
+}
+
+impl RequestCost for GetMempoolEntriesRequest {
+    fn cost(&self) -> u32 {
+        SCAN_COST
+    }
+}
+
+impl RequestCost for CreateBackupRequest {
+    fn cost(&self) -> u32 {
+        SCAN_COST
+    }
+}
+
+impl RequestCost for Scope {
+    fn cost(&self) -> u32 {
+        match self {
+            Scope::UtxosChanged(scope) => count_cost(scope.addresses.len()),
+            _ => UNIT_COST,
+        }
+    }
+}
+
+/// The rate limit of the RPC clients sharing an IP address
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RpcRateLimit {
+    /// Units of cost refilled per second, 0 meaning unlimited
+    pub rate: u32,
+    /// Maximum units of cost available at once
+    pub burst: u32,
+}
+
+impl RpcRateLimit {
+    pub fn new(rate: u32, burst: u32) -> Self {
+        Self { rate, burst }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.rate == 0
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// A token bucket shared by the connections of an IP address
+#[derive(Debug)]
+pub struct RpcRateLimiter {
+    limit: RpcRateLimit,
+    bucket: Mutex<Bucket>,
+}
+
+impl RpcRateLimiter {
+    pub fn new(limit: RpcRateLimit) -> Self {
+        Self { limit, bucket: Mutex::new(Bucket { tokens: limit.burst as f64, updated: Instant::now() }) }
+    }
+
+    /// Draws `cost` units from the bucket or returns [`RpcError::RateLimited`] with the delay
+    /// after which the request can be retried.
+    ///
+    /// A request costing more than the burst is accepted on a full bucket, the bucket then
+    /// owing the excess units.
+    pub fn try_acquire(&self, cost: u32) -> RpcResult<()> {
+        self.try_acquire_at(cost, Instant::now())
+    }
+
+    fn try_acquire_at(&self, cost: u32, now: Instant) -> RpcResult<()> {
+        let rate = self.limit.rate as f64;
+        let burst = self.limit.burst as f64;
+        let mut bucket = self.bucket.lock().unwrap();
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(burst);
+        bucket.updated = now;
+
+        let required = (cost as f64).min(burst);
+        if bucket.tokens >= required {
+            bucket.tokens -= cost as f64;
+            Ok(())
+        } else {
+            let retry_after = Duration::from_secs_f64((required - bucket.tokens) / rate);
+            Err(RpcError::RateLimited(retry_after.as_millis().max(1) as u64))
+        }
+    }
+}
+
+/// Maps the IP addresses of the RPC clients to their rate limiter
+#[derive(Debug, Default)]
+pub struct RpcRateLimiters {
+    limit: RpcRateLimit,
+    limiters: Mutex<HashMap<IpAddr, Weak<RpcRateLimiter>>>,
+}
+
+impl RpcRateLimiters {
+    pub fn new(limit: RpcRateLimit) -> Self {
+        Self { limit, limiters: Default::default() }
+    }
+
+    pub fn limit(&self) -> RpcRateLimit {
+        self.limit
+    }
+
+    /// Returns the rate limiter of the connections from `ip`, none if rates are unlimited.
+    ///
+    /// The limiter lives as long as some connection holds it.
+    pub fn get(&self, ip: IpAddr) -> Option<Arc<RpcRateLimiter>> {
+        if self.limit.is_unlimited() {
+            return None;
+        }
+        let mut limiters = self.limiters.lock().unwrap();
+        if let Some(limiter) = limiters.get(&ip).and_then(Weak::upgrade) {
+            return Some(limiter);
+        }
+        limiters.retain(|_, limiter| limiter.strong_count() > 0);
+        let limiter = Arc::new(RpcRateLimiter::new(self.limit));
+        limiters.insert(ip, Arc::downgrade(&limiter));
+        Some(limiter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RpcAddress;
+    use karlsen_addresses::{Prefix, Version};
+
+    #[test]
+    fn test_request_cost() {
+        let addresses = (0..250u8).map(|i| RpcAddress::new(Prefix::Mainnet, Version::PubKey, &[i; 32])).collect::<Vec<_>>();
+        assert_eq!(GetInfoRequest {}.cost(), UNIT_COST);
+        assert_eq!(GetUtxosByAddressesRequest::new(vec![]).cost(), UNIT_COST);
+        assert_eq!(GetUtxosByAddressesRequest::new(addresses.clone()).cost(), 250);
+        assert_eq!(GetBalancesByAddressesRequest::new(addresses).cost(), 250);
+        assert_eq!(GetBlocksRequest::new(None, true, true).cost(), 4 * SCAN_COST);
+        assert!(GetBlocksRequest::new(None, false, false).cost() > GetInfoRequest {}.cost());
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RpcRateLimiter::new(RpcRateLimit::new(10, 20));
+        let start = Instant::now();
+
+        // The burst is available at once
+        assert!(limiter.try_acquire_at(15, start).is_ok());
+        assert!(limiter.try_acquire_at(5, start).is_ok());
+        assert!(matches!(limiter.try_acquire_at(1, start), Err(RpcError::RateLimited(100))));
+
+        // The bucket refills at the rate
+        assert!(limiter.try_acquire_at(10, start + Duration::from_secs(1)).is_ok());
+        assert!(limiter.try_acquire_at(1, start + Duration::from_secs(1)).is_err());
+
+        // A request above the burst requires a full bucket and leaves a debt
+        assert!(matches!(limiter.try_acquire_at(50, start + Duration::from_secs(2)), Err(RpcError::RateLimited(1000))));
+        assert!(limiter.try_acquire_at(50, start + Duration::from_secs(3)).is_ok());
+        assert!(matches!(limiter.try_acquire_at(1, start + Duration::from_secs(3)), Err(RpcError::RateLimited(3100))));
+
+        // Clients recover the delay from the error message
+        let message = format!("RPC Server (remote error) -> {}", RpcError::RateLimited(250));
+        assert!(matches!(RpcError::from(message), RpcError::RateLimited(250)));
+    }
+
+    #[test]
+    fn test_rate_limiters() {
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        assert!(RpcRateLimiters::default().get(ip).is_none());
+
+        let limiters = RpcRateLimiters::new(RpcRateLimit::new(1, 1));
+        let limiter = limiters.get(ip).unwrap();
+        assert!(Arc::ptr_eq(&limiter, &limiters.get(ip).unwrap()));
+        assert!(!Arc::ptr_eq(&limiter, &limiters.get("10.0.0.2".parse().unwrap()).unwrap()));
+
+        // Connections of an IP share the bucket
+        assert!(limiter.try_acquire(1).is_ok());
+        assert!(limiters.get(ip).unwrap().try_acquire(1).is_err());
+    }
+}
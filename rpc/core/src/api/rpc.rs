@@ -15,6 +15,12 @@ use std::sync::Arc;
 
 pub const MAX_SAFE_WINDOW_SIZE: u32 = 10_000;
 
+/// Maximum number of transaction ids accepted by a single `GetTransactionsByIds` request
+pub const MAX_TRANSACTIONS_BY_IDS: usize = 1_000;
+
+/// Maximum number of entries returned by a single `GetAddressHistory` request
+pub const MAX_ADDRESS_HISTORY_ENTRIES: u32 = 1_000;
+
 /// Client RPC Api
 ///
 /// The [`RpcApi`] trait defines RPC calls taking a request message as unique parameter.
@@ -312,6 +318,16 @@ pub trait RpcApi: Sync + Send + AnySync {
     }
     async fn shutdown_call(&self, connection: Option<&DynRpcConnection>, request: ShutdownRequest) -> RpcResult<ShutdownResponse>;
 
+    /// Creates a consistent backup of the node databases within the backup directory of the node.
+    async fn create_backup(&self) -> RpcResult<CreateBackupResponse> {
+        self.create_backup_call(None, CreateBackupRequest {}).await
+    }
+    async fn create_backup_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: CreateBackupRequest,
+    ) -> RpcResult<CreateBackupResponse>;
+
     /// Requests headers between the given `start_hash` and the current virtual, up to the given limit.
     async fn get_headers(&self, start_hash: RpcHash, limit: u64, is_ascending: bool) -> RpcResult<Vec<RpcHeader>> {
         Ok(self.get_headers_call(None, GetHeadersRequest::new(start_hash, limit, is_ascending)).await?.headers)
@@ -451,6 +467,40 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetUtxoReturnAddressRequest,
     ) -> RpcResult<GetUtxoReturnAddressResponse>;
 
+    async fn get_transaction(&self, transaction_id: RpcTransactionId) -> RpcResult<RpcIndexedTransaction> {
+        Ok(self.get_transaction_call(None, GetTransactionRequest { transaction_id }).await?.transaction)
+    }
+    async fn get_transaction_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse>;
+
+    async fn get_transactions_by_ids(&self, transaction_ids: Vec<RpcTransactionId>) -> RpcResult<Vec<RpcIndexedTransaction>> {
+        Ok(self.get_transactions_by_ids_call(None, GetTransactionsByIdsRequest { transaction_ids }).await?.transactions)
+    }
+    async fn get_transactions_by_ids_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetTransactionsByIdsRequest,
+    ) -> RpcResult<GetTransactionsByIdsResponse>;
+
+    async fn get_address_history(
+        &self,
+        address: RpcAddress,
+        start_daa_score: Option<u64>,
+        end_daa_score: Option<u64>,
+        cursor: Option<String>,
+        limit: u32,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        self.get_address_history_call(None, GetAddressHistoryRequest { address, start_daa_score, end_daa_score, cursor, limit }).await
+    }
+    async fn get_address_history_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Fee estimation API
 
@@ -63,6 +63,24 @@ pub enum RpcError {
     #[error("Method unavailable. Run the node with the --utxoindex argument.")]
     NoUtxoIndex,
 
+    #[error("Method unavailable. Run the node with the --txindex argument.")]
+    NoTxIndex,
+
+    #[error("Method unavailable. Run the node with the --addresshistoryindex argument.")]
+    NoAddressHistoryIndex,
+
+    #[error("Method unavailable. Run the node with the --backupdir argument.")]
+    NoBackupDir,
+
+    #[error("Backup failed: {0}")]
+    BackupError(String),
+
+    #[error("Invalid address history cursor {0}")]
+    InvalidAddressHistoryCursor(String),
+
+    #[error("Requested {0} transactions, more than the max {1} allowed.")]
+    TransactionIdsExceedingMaximum(usize, usize),
+
     #[error("Method unavailable. No connection manager is currently available.")]
     NoConnectionManager,
 
@@ -75,6 +93,21 @@ pub enum RpcError {
     #[error("Method unavailable in safe mode. Run the node with --unsaferpc argument.")]
     UnavailableInSafeMode,
 
+    #[error("Block template notifications require a pay address")]
+    MissingBlockTemplatePayAddress,
+
+    #[error("Authentication required")]
+    Unauthenticated,
+
+    #[error("Invalid authentication token")]
+    InvalidAuthToken,
+
+    #[error("Method {0} is not allowed for the role {1}")]
+    AccessDenied(String, String),
+
+    #[error("Rate limit exceeded, retry in {0} ms")]
+    RateLimited(u64),
+
     #[error("Cannot ban IP {0} because it has some permanent connection.")]
     IpHasPermanentConnection(IpAddress),
 
@@ -139,15 +172,36 @@ pub enum RpcError {
     UtxoReturnAddressNotFound(UtxoInquirerError),
 }
 
+impl RpcError {
+    /// Recovers the delay of a [`RpcError::RateLimited`] error from its message
+    fn parse_rate_limited(message: &str) -> Option<u64> {
+        const PREFIX: &str = "Rate limit exceeded, retry in ";
+        let start = message.find(PREFIX)? + PREFIX.len();
+        message[start..].split_once(" ms")?.0.parse().ok()
+    }
+
+    /// Builds the error of a failed wRPC call from its message
+    pub fn from_remote(message: String) -> Self {
+        match RpcError::parse_rate_limited(&message) {
+            Some(retry_after) => RpcError::RateLimited(retry_after),
+            None => RpcError::RpcSubsystem(message),
+        }
+    }
+}
+
+// Errors cross the wire as their message so the variants clients are expected to react to are restored
 impl From<String> for RpcError {
     fn from(value: String) -> Self {
-        RpcError::General(value)
+        match RpcError::parse_rate_limited(&value) {
+            Some(retry_after) => RpcError::RateLimited(retry_after),
+            None => RpcError::General(value),
+        }
     }
 }
 
 impl From<&str> for RpcError {
     fn from(value: &str) -> Self {
-        RpcError::General(value.to_string())
+        value.to_string().into()
     }
 }
 
@@ -1,4 +1,5 @@
-use crate::{RpcTransactionOutpoint, RpcUtxoEntry};
+use crate::{RpcHash, RpcTransactionId, RpcTransactionOutpoint, RpcUtxoEntry};
+use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
@@ -58,3 +59,58 @@ impl Deserializer for RpcBalancesByAddressesEntry {
         Ok(Self { address, balance })
     }
 }
+
+/// Whether an [`RpcAddressHistoryEntry`] credits or debits its address.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[borsh(use_discriminant = true)]
+pub enum RpcAddressHistoryEntryKind {
+    /// An output paying to the address
+    Received = 0,
+    /// An input spending an output previously paid to the address
+    Spent = 1,
+}
+
+/// Represents a balance change of an address returned by the `GetAddressHistory` RPC.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAddressHistoryEntry {
+    pub transaction_id: RpcTransactionId,
+    pub kind: RpcAddressHistoryEntryKind,
+
+    /// The output index for received entries, the input index for spent entries
+    pub index: u32,
+
+    /// The outpoint created (received) or consumed (spent) by the transaction
+    pub outpoint: RpcTransactionOutpoint,
+    pub amount: u64,
+    pub accepting_block_hash: RpcHash,
+    pub accepting_block_daa_score: u64,
+}
+
+impl Serializer for RpcAddressHistoryEntry {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?; // version
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(RpcAddressHistoryEntryKind, &self.kind, writer)?;
+        store!(u32, &self.index, writer)?;
+        serialize!(RpcTransactionOutpoint, &self.outpoint, writer)?;
+        store!(u64, &self.amount, writer)?;
+        store!(RpcHash, &self.accepting_block_hash, writer)?;
+        store!(u64, &self.accepting_block_daa_score, writer)
+    }
+}
+
+impl Deserializer for RpcAddressHistoryEntry {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version: u8 = load!(u8, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let kind = load!(RpcAddressHistoryEntryKind, reader)?;
+        let index = load!(u32, reader)?;
+        let outpoint = deserialize!(RpcTransactionOutpoint, reader)?;
+        let amount = load!(u64, reader)?;
+        let accepting_block_hash = load!(RpcHash, reader)?;
+        let accepting_block_daa_score = load!(u64, reader)?;
+        Ok(Self { transaction_id, kind, index, outpoint, amount, accepting_block_hash, accepting_block_daa_score })
+    }
+}
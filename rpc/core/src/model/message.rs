@@ -2,7 +2,11 @@ use crate::model::*;
 use borsh::{BorshDeserialize, BorshSerialize};
 use karlsen_consensus_core::api::stats::BlockCount;
 use karlsen_core::debug;
-use karlsen_notify::subscription::{context::SubscriptionContext, single::UtxosChangedSubscription, Command};
+use karlsen_notify::subscription::{
+    context::SubscriptionContext,
+    single::{BlockTemplateSubscription, UtxosChangedSubscription},
+    Command,
+};
 use karlsen_utils::hex::ToHex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -2733,6 +2737,274 @@ impl Deserializer for GetUtxoReturnAddressResponse {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionRequest {
+    pub transaction_id: RpcTransactionId,
+}
+
+impl GetTransactionRequest {
+    pub fn new(transaction_id: RpcTransactionId) -> Self {
+        Self { transaction_id }
+    }
+}
+
+impl Serializer for GetTransactionRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+
+        Ok(Self { transaction_id })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionResponse {
+    pub transaction: RpcIndexedTransaction,
+}
+
+impl GetTransactionResponse {
+    pub fn new(transaction: RpcIndexedTransaction) -> Self {
+        Self { transaction }
+    }
+}
+
+impl Serializer for GetTransactionResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcIndexedTransaction, &self.transaction, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction = deserialize!(RpcIndexedTransaction, reader)?;
+
+        Ok(Self { transaction })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionsByIdsRequest {
+    pub transaction_ids: Vec<RpcTransactionId>,
+}
+
+impl GetTransactionsByIdsRequest {
+    pub fn new(transaction_ids: Vec<RpcTransactionId>) -> Self {
+        Self { transaction_ids }
+    }
+}
+
+impl Serializer for GetTransactionsByIdsRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcTransactionId>, &self.transaction_ids, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionsByIdsRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+
+        Ok(Self { transaction_ids })
+    }
+}
+
+/// Transactions which are not found in the transaction index are omitted from the response.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionsByIdsResponse {
+    pub transactions: Vec<RpcIndexedTransaction>,
+}
+
+impl GetTransactionsByIdsResponse {
+    pub fn new(transactions: Vec<RpcIndexedTransaction>) -> Self {
+        Self { transactions }
+    }
+}
+
+impl Serializer for GetTransactionsByIdsResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcIndexedTransaction>, &self.transactions, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionsByIdsResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transactions = deserialize!(Vec<RpcIndexedTransaction>, reader)?;
+
+        Ok(Self { transactions })
+    }
+}
+
+/// GetAddressHistoryRequest requests a page of the history of an address from the address history index.
+///
+/// Entries are ordered by accepting DAA score and restricted to the inclusive `[start_daa_score, end_daa_score]`
+/// range when bounds are provided. Passing the `next_cursor` of a previous response resumes right after its last entry.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressHistoryRequest {
+    pub address: RpcAddress,
+    pub start_daa_score: Option<u64>,
+    pub end_daa_score: Option<u64>,
+    /// Opaque hex encoded cursor returned by a previous response
+    pub cursor: Option<String>,
+    /// Maximum number of entries returned, capped by the node
+    pub limit: u32,
+}
+
+impl GetAddressHistoryRequest {
+    pub fn new(
+        address: RpcAddress,
+        start_daa_score: Option<u64>,
+        end_daa_score: Option<u64>,
+        cursor: Option<String>,
+        limit: u32,
+    ) -> Self {
+        Self { address, start_daa_score, end_daa_score, cursor, limit }
+    }
+}
+
+impl Serializer for GetAddressHistoryRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcAddress, &self.address, writer)?;
+        store!(Option<u64>, &self.start_daa_score, writer)?;
+        store!(Option<u64>, &self.end_daa_score, writer)?;
+        store!(Option<String>, &self.cursor, writer)?;
+        store!(u32, &self.limit, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetAddressHistoryRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let address = load!(RpcAddress, reader)?;
+        let start_daa_score = load!(Option<u64>, reader)?;
+        let end_daa_score = load!(Option<u64>, reader)?;
+        let cursor = load!(Option<String>, reader)?;
+        let limit = load!(u32, reader)?;
+
+        Ok(Self { address, start_daa_score, end_daa_score, cursor, limit })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressHistoryResponse {
+    pub entries: Vec<RpcAddressHistoryEntry>,
+    /// The cursor to pass in order to query the next page, `None` if this is the last page
+    pub next_cursor: Option<String>,
+}
+
+impl GetAddressHistoryResponse {
+    pub fn new(entries: Vec<RpcAddressHistoryEntry>, next_cursor: Option<String>) -> Self {
+        Self { entries, next_cursor }
+    }
+}
+
+impl Serializer for GetAddressHistoryResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcAddressHistoryEntry>, &self.entries, writer)?;
+        store!(Option<String>, &self.next_cursor, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetAddressHistoryResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let entries = deserialize!(Vec<RpcAddressHistoryEntry>, reader)?;
+        let next_cursor = load!(Option<String>, reader)?;
+
+        Ok(Self { entries, next_cursor })
+    }
+}
+
+/// CreateBackupRequest creates a consistent checkpoint of the node databases in a new directory within the
+/// backup directory of the node, along with a manifest describing it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBackupRequest {}
+
+impl Serializer for CreateBackupRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for CreateBackupRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBackupResponse {
+    /// Path of the backup directory on the node host
+    pub path: String,
+    /// The sink recorded in the backup manifest
+    pub sink: RpcHash,
+    /// The pruning point recorded in the backup manifest
+    pub pruning_point: RpcHash,
+}
+
+impl CreateBackupResponse {
+    pub fn new(path: String, sink: RpcHash, pruning_point: RpcHash) -> Self {
+        Self { path, sink, pruning_point }
+    }
+}
+
+impl Serializer for CreateBackupResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.path, writer)?;
+        store!(RpcHash, &self.sink, writer)?;
+        store!(RpcHash, &self.pruning_point, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for CreateBackupResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let path = load!(String, reader)?;
+        let sink = load!(RpcHash, reader)?;
+        let pruning_point = load!(RpcHash, reader)?;
+
+        Ok(Self { path, sink, pruning_point })
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Subscriptions & notifications
 // ----------------------------------------------------------------------------
@@ -3465,6 +3737,130 @@ impl Deserializer for NewBlockTemplateNotification {
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// BlockTemplateNotification
+
+/// NotifyBlockTemplateRequest registers this connection for BlockTemplate notifications, pushing a full block template
+/// paying to `pay_address` whenever the template changes.
+///
+/// See: BlockTemplateNotification
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyBlockTemplateRequest {
+    /// Which karlsen address should the coinbase block reward transaction pay into.
+    /// Required when starting the notifications.
+    pub pay_address: Option<RpcAddress>,
+    pub extra_data: RpcExtraData,
+    pub command: Command,
+}
+impl NotifyBlockTemplateRequest {
+    pub fn new(pay_address: Option<RpcAddress>, extra_data: RpcExtraData, command: Command) -> Self {
+        Self { pay_address, extra_data, command }
+    }
+}
+
+impl Serializer for NotifyBlockTemplateRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Option<RpcAddress>, &self.pay_address, writer)?;
+        store!(RpcExtraData, &self.extra_data, writer)?;
+        store!(Command, &self.command, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyBlockTemplateRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let pay_address = load!(Option<RpcAddress>, reader)?;
+        let extra_data = load!(RpcExtraData, reader)?;
+        let command = load!(Command, reader)?;
+        Ok(Self { pay_address, extra_data, command })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyBlockTemplateResponse {}
+
+impl Serializer for NotifyBlockTemplateResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyBlockTemplateResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// Builds the block templates carried by BlockTemplate notifications.
+///
+/// Implemented by the node, which attaches it to the notifications it emits so every subscription
+/// gets a template paying to its own address.
+pub trait BlockTemplateSource: Send + Sync + std::fmt::Debug {
+    /// Returns the current block template and whether the node is synced, or `None` if no template can be built
+    fn build(&self, pay_address: &RpcAddress, extra_data: &[u8]) -> Option<(RpcRawBlock, bool)>;
+}
+
+/// BlockTemplateNotification is sent whenever the block template changes, carrying a template built for the
+/// pay address and extra data of the subscription.
+///
+/// See: NotifyBlockTemplateRequest
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTemplateNotification {
+    /// The block template, only missing from a notification not yet applied to a subscription
+    pub block: Option<Arc<RpcRawBlock>>,
+
+    /// Whether karlsend thinks that it's synced (see [`GetBlockTemplateResponse::is_synced`])
+    pub is_synced: bool,
+
+    #[serde(skip)]
+    pub source: Option<Arc<dyn BlockTemplateSource>>,
+}
+
+impl BlockTemplateNotification {
+    pub fn with_source(source: Arc<dyn BlockTemplateSource>) -> Self {
+        Self { block: None, is_synced: false, source: Some(source) }
+    }
+
+    pub(crate) fn apply_block_template_subscription(&self, subscription: &BlockTemplateSubscription) -> Option<Self> {
+        match (self.source.as_ref(), subscription.pay_address()) {
+            (Some(source), Some(pay_address)) => {
+                let (block, is_synced) = source.build(pay_address, subscription.extra_data())?;
+                // The source is kept so that downstream notifiers build the templates of their own subscriptions
+                Some(Self { block: Some(Arc::new(block)), is_synced, source: Some(source.clone()) })
+            }
+            _ => Some(self.clone()),
+        }
+    }
+}
+
+impl Serializer for BlockTemplateNotification {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(bool, &self.block.is_some(), writer)?;
+        if let Some(ref block) = self.block {
+            serialize!(RpcRawBlock, block, writer)?;
+        }
+        store!(bool, &self.is_synced, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for BlockTemplateNotification {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let block = if load!(bool, reader)? { Some(Arc::new(deserialize!(RpcRawBlock, reader)?)) } else { None };
+        let is_synced = load!(bool, reader)?;
+        Ok(Self { block, is_synced, source: None })
+    }
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
@@ -3515,3 +3911,60 @@ impl Deserializer for UnsubscribeResponse {
         Ok(Self {})
     }
 }
+
+///
+///  wRPC request for RpcApiOps::Authenticate, presenting a token granting the connection the role it is mapped to
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticateRequest {
+    pub token: String,
+}
+
+impl AuthenticateRequest {
+    pub fn new(token: String) -> Self {
+        Self { token }
+    }
+}
+
+impl Serializer for AuthenticateRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.token, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for AuthenticateRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let token = load!(String, reader)?;
+        Ok(Self { token })
+    }
+}
+
+///
+///  wRPC response for RpcApiOps::Authenticate request
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticateResponse {
+    /// The name of the role granted to the connection
+    pub role: String,
+}
+
+impl Serializer for AuthenticateResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.role, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for AuthenticateResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let role = load!(String, reader)?;
+        Ok(Self { role })
+    }
+}
@@ -1288,6 +1288,30 @@ mod mockery {
 
     test!(NewBlockTemplateNotification);
 
+    impl Mock for NotifyBlockTemplateRequest {
+        fn mock() -> Self {
+            NotifyBlockTemplateRequest { pay_address: Some(mock()), extra_data: vec![4, 2], command: Command::Start }
+        }
+    }
+
+    test!(NotifyBlockTemplateRequest);
+
+    impl Mock for NotifyBlockTemplateResponse {
+        fn mock() -> Self {
+            NotifyBlockTemplateResponse {}
+        }
+    }
+
+    test!(NotifyBlockTemplateResponse);
+
+    impl Mock for BlockTemplateNotification {
+        fn mock() -> Self {
+            BlockTemplateNotification { block: Some(mock()), is_synced: true, source: None }
+        }
+    }
+
+    test!(BlockTemplateNotification);
+
     impl Mock for SubscribeResponse {
         fn mock() -> Self {
             SubscribeResponse::new(mock())
@@ -398,3 +398,44 @@ pub struct RpcAcceptedTransactionIds {
     pub accepting_block_hash: RpcHash,
     pub accepted_transaction_ids: Vec<RpcTransactionId>,
 }
+
+/// Represents a transaction located by the transaction index
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcIndexedTransaction {
+    pub transaction_id: RpcTransactionId,
+    /// The transaction itself, or `None` if the containing block body was pruned
+    pub transaction: Option<RpcTransaction>,
+    pub block_hash: RpcHash,
+    pub index_within_block: u32,
+    pub accepting_block_hash: RpcHash,
+    pub accepting_block_daa_score: u64,
+}
+
+impl Serializer for RpcIndexedTransaction {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        serialize!(Option<RpcTransaction>, &self.transaction, writer)?;
+        store!(RpcHash, &self.block_hash, writer)?;
+        store!(u32, &self.index_within_block, writer)?;
+        store!(RpcHash, &self.accepting_block_hash, writer)?;
+        store!(u64, &self.accepting_block_daa_score, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcIndexedTransaction {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let transaction = deserialize!(Option<RpcTransaction>, reader)?;
+        let block_hash = load!(RpcHash, reader)?;
+        let index_within_block = load!(u32, reader)?;
+        let accepting_block_hash = load!(RpcHash, reader)?;
+        let accepting_block_daa_score = load!(u64, reader)?;
+
+        Ok(Self { transaction_id, transaction, block_hash, index_within_block, accepting_block_hash, accepting_block_daa_score })
+    }
+}
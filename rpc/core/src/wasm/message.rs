@@ -1769,4 +1769,188 @@ try_from!(args: GetUtxoReturnAddressResponse, IGetUtxoReturnAddressResponse, {
     Ok(to_value(&args)?.into())
 });
 
+declare! {
+    IGetTransactionRequest,
+    r#"
+    /**
+     * Requests a transaction by id from the transaction index.
+     * Requires the node to run with `--txindex`.
+     *
+     * @category Node RPC
+     */
+    export interface IGetTransactionRequest {
+        transactionId: HexString;
+    }
+    "#,
+}
+
+try_from!(args: IGetTransactionRequest, GetTransactionRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionResponse,
+    r#"
+    /**
+     *
+     *
+     * @category Node RPC
+     */
+    export interface IGetTransactionResponse {
+        transaction: {
+            transactionId: HexString;
+            transaction?: ITransaction;
+            blockHash: HexString;
+            indexWithinBlock: number;
+            acceptingBlockHash: HexString;
+            acceptingBlockDaaScore: bigint;
+        };
+    }
+    "#,
+}
+
+try_from!(args: GetTransactionResponse, IGetTransactionResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+declare! {
+    IGetTransactionsByIdsRequest,
+    r#"
+    /**
+     * Requests a set of transactions by ids from the transaction index.
+     * Requires the node to run with `--txindex`.
+     *
+     * @category Node RPC
+     */
+    export interface IGetTransactionsByIdsRequest {
+        transactionIds: HexString[];
+    }
+    "#,
+}
+
+try_from!(args: IGetTransactionsByIdsRequest, GetTransactionsByIdsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionsByIdsResponse,
+    r#"
+    /**
+     * Transactions which are not indexed are omitted.
+     *
+     * @category Node RPC
+     */
+    export interface IGetTransactionsByIdsResponse {
+        transactions: {
+            transactionId: HexString;
+            transaction?: ITransaction;
+            blockHash: HexString;
+            indexWithinBlock: number;
+            acceptingBlockHash: HexString;
+            acceptingBlockDaaScore: bigint;
+        }[];
+    }
+    "#,
+}
+
+try_from!(args: GetTransactionsByIdsResponse, IGetTransactionsByIdsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+declare! {
+    IGetAddressHistoryRequest,
+    r#"
+    /**
+     * Requests a page of the history of an address from the address history index.
+     * Requires the node to run with `--addresshistoryindex`.
+     *
+     * @category Node RPC
+     */
+    export interface IGetAddressHistoryRequest {
+        address : Address | string;
+        startDaaScore? : bigint;
+        endDaaScore? : bigint;
+        /**
+         * The `nextCursor` of a previous response.
+         */
+        cursor? : HexString;
+        limit : number;
+    }
+    "#,
+}
+
+try_from!(args: IGetAddressHistoryRequest, GetAddressHistoryRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetAddressHistoryResponse,
+    r#"
+    /**
+     * Entries are ordered by accepting DAA score.
+     *
+     * @category Node RPC
+     */
+    export interface IGetAddressHistoryResponse {
+        entries: {
+            transactionId: HexString;
+            kind: "received" | "spent";
+            index: number;
+            outpoint: {
+                transactionId: HexString;
+                index: number;
+            };
+            amount: bigint;
+            acceptingBlockHash: HexString;
+            acceptingBlockDaaScore: bigint;
+        }[];
+        nextCursor?: HexString;
+    }
+    "#,
+}
+
+try_from!(args: GetAddressHistoryResponse, IGetAddressHistoryResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    ICreateBackupRequest,
+    r#"
+    /**
+     * Creates a backup of the node databases within its backup directory.
+     * Requires the node to run with `--backupdir`.
+     *
+     * @category Node RPC
+     */
+    export interface ICreateBackupRequest { }
+    "#,
+}
+
+try_from!(args: ICreateBackupRequest, CreateBackupRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    ICreateBackupResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface ICreateBackupResponse {
+        /**
+         * Path of the backup directory on the node host.
+         */
+        path : string;
+        sink : HexString;
+        pruningPoint : HexString;
+    }
+    "#,
+}
+
+try_from!(args: CreateBackupResponse, ICreateBackupResponse, {
+    Ok(to_value(&args)?.into())
+});
+
 // ---
@@ -85,7 +85,7 @@ impl GrpcClient {
     pub const DIRECT_MODE_LISTENER_ID: ListenerId = 0;
 
     pub async fn connect(url: String) -> Result<GrpcClient> {
-        Self::connect_with_args(NotificationMode::Direct, url, None, false, None, false, None, Default::default()).await
+        Self::connect_with_args(NotificationMode::Direct, url, None, false, None, false, None, Default::default(), None).await
     }
 
     /// Connects to a gRPC server.
@@ -112,6 +112,8 @@ impl GrpcClient {
     /// `timeout_duration`: request timeout duration
     ///
     /// `counters`: collects some bandwidth metrics
+    ///
+    /// `auth_token`: token granting the client a role on servers restricting the RPC methods, sent as a bearer `authorization` metadata
     pub async fn connect_with_args(
         notification_mode: NotificationMode,
         url: String,
@@ -121,6 +123,7 @@ impl GrpcClient {
         override_handle_stop_notify: bool,
         timeout_duration: Option<u64>,
         counters: Arc<TowerConnectionCounters>,
+        auth_token: Option<String>,
     ) -> Result<GrpcClient> {
         let schema = Regex::new(r"^grpc://").unwrap();
         if !schema.is_match(&url) {
@@ -132,6 +135,7 @@ impl GrpcClient {
             override_handle_stop_notify,
             timeout_duration.unwrap_or(REQUEST_TIMEOUT_DURATION),
             counters,
+            auth_token,
         )
         .await?;
         let converter = Arc::new(RpcCoreConverter::new());
@@ -277,6 +281,10 @@ impl RpcApi for GrpcClient {
     route!(get_fee_estimate_experimental_call, GetFeeEstimateExperimental);
     route!(get_current_block_color_call, GetCurrentBlockColor);
     route!(get_utxo_return_address_call, GetUtxoReturnAddress);
+    route!(get_transaction_call, GetTransaction);
+    route!(get_transactions_by_ids_call, GetTransactionsByIds);
+    route!(get_address_history_call, GetAddressHistory);
+    route!(create_backup_call, CreateBackup);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
@@ -439,6 +447,9 @@ struct Inner {
 
     // bandwidth counters
     counters: Arc<TowerConnectionCounters>,
+
+    // Token presented to the server on every connection
+    auth_token: Option<String>,
 }
 
 impl Inner {
@@ -451,6 +462,7 @@ impl Inner {
         override_handle_stop_notify: bool,
         timeout_duration: u64,
         counters: Arc<TowerConnectionCounters>,
+        auth_token: Option<String>,
     ) -> Self {
         let resolver: DynResolver = match server_features.handle_message_id {
             true => Arc::new(IdResolver::new()),
@@ -476,6 +488,7 @@ impl Inner {
             connection_event_sender,
             override_handle_stop_notify,
             counters,
+            auth_token,
         }
     }
 
@@ -486,14 +499,21 @@ impl Inner {
         override_handle_stop_notify: bool,
         timeout_duration: u64,
         counters: Arc<TowerConnectionCounters>,
+        auth_token: Option<String>,
     ) -> Result<Arc<Self>> {
         // Request channel
         let (request_sender, request_receiver) = async_channel::unbounded();
 
         // Try to connect to the server
-        let (stream, server_features) =
-            Inner::try_connect(url.clone(), request_sender.clone(), request_receiver.clone(), timeout_duration, counters.clone())
-                .await?;
+        let (stream, server_features) = Inner::try_connect(
+            url.clone(),
+            request_sender.clone(),
+            request_receiver.clone(),
+            timeout_duration,
+            counters.clone(),
+            auth_token.as_deref(),
+        )
+        .await?;
 
         // create the inner object
         let inner = Arc::new(Inner::new(
@@ -505,6 +525,7 @@ impl Inner {
             override_handle_stop_notify,
             timeout_duration,
             counters,
+            auth_token,
         ));
 
         // Start the request timeout cleaner
@@ -524,6 +545,7 @@ impl Inner {
         request_receiver: KarlsendRequestReceiver,
         request_timeout: u64,
         counters: Arc<TowerConnectionCounters>,
+        auth_token: Option<&str>,
     ) -> Result<(Streaming<KarlsendResponse>, ServerFeatures)> {
         // gRPC endpoint
         #[cfg(not(feature = "heap"))]
@@ -572,8 +594,15 @@ impl Inner {
             }
         };
 
+        // Present the token, if any, in the metadata of the stream request
+        let mut request = tonic::Request::new(request_stream);
+        if let Some(token) = auth_token {
+            let value = format!("Bearer {token}").parse().map_err(|_| Error::String("invalid authentication token".to_string()))?;
+            request.metadata_mut().insert("authorization", value);
+        }
+
         // Actual KarlsendRequest to KarlsendResponse stream
-        let mut stream: Streaming<KarlsendResponse> = client.message_stream(request_stream).await?.into_inner();
+        let mut stream: Streaming<KarlsendResponse> = client.message_stream(request).await?.into_inner();
 
         // Collect server capabilities as stated in GetInfoResponse
         let mut server_features = ServerFeatures::default();
@@ -616,6 +645,7 @@ impl Inner {
             self.request_receiver.clone(),
             self.timeout_duration,
             self.counters.clone(),
+            self.auth_token.as_deref(),
         )
         .await?;
 
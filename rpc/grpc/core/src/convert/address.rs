@@ -1,6 +1,7 @@
-use crate::protowire;
+use crate::protowire::{self, rpc_address_history_entry::Kind};
 use crate::{from, try_from};
-use karlsen_rpc_core::RpcError;
+use karlsen_rpc_core::{RpcAddressHistoryEntryKind, RpcError, RpcHash};
+use std::str::FromStr;
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -10,6 +11,25 @@ from!(item: &karlsen_rpc_core::RpcBalancesByAddressesEntry, protowire::RpcBalanc
     Self { address: (&item.address).into(), balance: item.balance.unwrap_or_default(), error: None }
 });
 
+from!(item: &RpcAddressHistoryEntryKind, Kind, {
+    match item {
+        RpcAddressHistoryEntryKind::Received => Kind::Received,
+        RpcAddressHistoryEntryKind::Spent => Kind::Spent,
+    }
+});
+
+from!(item: &karlsen_rpc_core::RpcAddressHistoryEntry, protowire::RpcAddressHistoryEntry, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        kind: Kind::from(&item.kind) as i32,
+        index: item.index,
+        outpoint: Some((&item.outpoint).into()),
+        amount: item.amount,
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        accepting_block_daa_score: item.accepting_block_daa_score,
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -18,3 +38,26 @@ try_from!(item: &protowire::RpcBalancesByAddressesEntry, karlsen_rpc_core::RpcBa
     let balance = if item.error.is_some() { None } else { Some(item.balance) };
     Self { address: item.address.as_str().try_into()?, balance }
 });
+
+from!(item: Kind, RpcAddressHistoryEntryKind, {
+    match item {
+        Kind::Received => RpcAddressHistoryEntryKind::Received,
+        Kind::Spent => RpcAddressHistoryEntryKind::Spent,
+    }
+});
+
+try_from!(item: &protowire::RpcAddressHistoryEntry, karlsen_rpc_core::RpcAddressHistoryEntry, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        kind: Kind::try_from(item.kind).map_err(|_| RpcError::PrimitiveToEnumConversionError)?.into(),
+        index: item.index,
+        outpoint: item
+            .outpoint
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("RpcAddressHistoryEntry".to_string(), "outpoint".to_string()))?
+            .try_into()?,
+        amount: item.amount,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+        accepting_block_daa_score: item.accepting_block_daa_score,
+    }
+});
@@ -64,6 +64,10 @@ pub mod karlsend_request_convert {
     impl_into_karlsend_request!(GetFeeEstimateExperimental);
     impl_into_karlsend_request!(GetCurrentBlockColor);
     impl_into_karlsend_request!(GetUtxoReturnAddress);
+    impl_into_karlsend_request!(GetTransaction);
+    impl_into_karlsend_request!(GetTransactionsByIds);
+    impl_into_karlsend_request!(GetAddressHistory);
+    impl_into_karlsend_request!(CreateBackup);
 
     impl_into_karlsend_request!(NotifyBlockAdded);
     impl_into_karlsend_request!(NotifyNewBlockTemplate);
@@ -72,6 +76,7 @@ pub mod karlsend_request_convert {
     impl_into_karlsend_request!(NotifyFinalityConflict);
     impl_into_karlsend_request!(NotifyVirtualDaaScoreChanged);
     impl_into_karlsend_request!(NotifyVirtualChainChanged);
+    impl_into_karlsend_request!(NotifyBlockTemplate);
     impl_into_karlsend_request!(NotifySinkBlueScoreChanged);
 
     macro_rules! impl_into_karlsend_request {
@@ -202,6 +207,10 @@ pub mod karlsend_response_convert {
     impl_into_karlsend_response!(GetFeeEstimateExperimental);
     impl_into_karlsend_response!(GetCurrentBlockColor);
     impl_into_karlsend_response!(GetUtxoReturnAddress);
+    impl_into_karlsend_response!(GetTransaction);
+    impl_into_karlsend_response!(GetTransactionsByIds);
+    impl_into_karlsend_response!(GetAddressHistory);
+    impl_into_karlsend_response!(CreateBackup);
 
     impl_into_karlsend_notify_response!(NotifyBlockAdded);
     impl_into_karlsend_notify_response!(NotifyNewBlockTemplate);
@@ -210,6 +219,7 @@ pub mod karlsend_response_convert {
     impl_into_karlsend_notify_response!(NotifyFinalityConflict);
     impl_into_karlsend_notify_response!(NotifyVirtualDaaScoreChanged);
     impl_into_karlsend_notify_response!(NotifyVirtualChainChanged);
+    impl_into_karlsend_notify_response!(NotifyBlockTemplate);
     impl_into_karlsend_notify_response!(NotifySinkBlueScoreChanged);
 
     impl_into_karlsend_notify_response!(NotifyUtxosChanged, StopNotifyingUtxosChanged);
@@ -196,6 +196,15 @@ from!(item: &karlsen_rpc_core::NotifyNewBlockTemplateRequest, protowire::NotifyN
 });
 from!(RpcResult<&karlsen_rpc_core::NotifyNewBlockTemplateResponse>, protowire::NotifyNewBlockTemplateResponseMessage);
 
+from!(item: &karlsen_rpc_core::NotifyBlockTemplateRequest, protowire::NotifyBlockTemplateRequestMessage, {
+    Self {
+        pay_address: item.pay_address.as_ref().map(|x| x.into()).unwrap_or_default(),
+        extra_data: String::from_utf8(item.extra_data.clone()).expect("extra data has to be valid UTF-8"),
+        command: item.command.into(),
+    }
+});
+from!(RpcResult<&karlsen_rpc_core::NotifyBlockTemplateResponse>, protowire::NotifyBlockTemplateResponseMessage);
+
 // ~~~
 
 from!(&karlsen_rpc_core::GetCurrentNetworkRequest, protowire::GetCurrentNetworkRequestMessage);
@@ -443,6 +452,43 @@ from!(item: RpcResult<&karlsen_rpc_core::GetUtxoReturnAddressResponse>, protowir
     Self { return_address: item.return_address.address_to_string(), error: None }
 });
 
+from!(item: &karlsen_rpc_core::GetTransactionRequest, protowire::GetTransactionRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string() }
+});
+from!(item: RpcResult<&karlsen_rpc_core::GetTransactionResponse>, protowire::GetTransactionResponseMessage, {
+    Self { transaction: Some((&item.transaction).into()), error: None }
+});
+
+from!(item: &karlsen_rpc_core::GetTransactionsByIdsRequest, protowire::GetTransactionsByIdsRequestMessage, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect() }
+});
+from!(item: RpcResult<&karlsen_rpc_core::GetTransactionsByIdsResponse>, protowire::GetTransactionsByIdsResponseMessage, {
+    Self { transactions: item.transactions.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &karlsen_rpc_core::GetAddressHistoryRequest, protowire::GetAddressHistoryRequestMessage, {
+    Self {
+        address: (&item.address).into(),
+        start_daa_score: item.start_daa_score,
+        end_daa_score: item.end_daa_score,
+        cursor: item.cursor.clone(),
+        limit: item.limit,
+    }
+});
+from!(item: RpcResult<&karlsen_rpc_core::GetAddressHistoryResponse>, protowire::GetAddressHistoryResponseMessage, {
+    Self { entries: item.entries.iter().map(|x| x.into()).collect(), next_cursor: item.next_cursor.clone(), error: None }
+});
+
+from!(&karlsen_rpc_core::CreateBackupRequest, protowire::CreateBackupRequestMessage);
+from!(item: RpcResult<&karlsen_rpc_core::CreateBackupResponse>, protowire::CreateBackupResponseMessage, {
+    Self {
+        path: item.path.clone(),
+        sink: item.sink.to_string(),
+        pruning_point: item.pruning_point.to_string(),
+        error: None,
+    }
+});
+
 from!(&karlsen_rpc_core::PingRequest, protowire::PingRequestMessage);
 from!(RpcResult<&karlsen_rpc_core::PingResponse>, protowire::PingResponseMessage);
 
@@ -660,6 +706,17 @@ try_from!(item: &protowire::NotifyNewBlockTemplateRequestMessage, karlsen_rpc_co
 });
 try_from!(&protowire::NotifyNewBlockTemplateResponseMessage, RpcResult<karlsen_rpc_core::NotifyNewBlockTemplateResponse>);
 
+try_from!(item: &protowire::NotifyBlockTemplateRequestMessage, karlsen_rpc_core::NotifyBlockTemplateRequest, {
+    let command: Command = item.command.into();
+    let pay_address = match (item.pay_address.is_empty(), command) {
+        (true, Command::Start) => Err(RpcError::MissingBlockTemplatePayAddress)?,
+        (true, Command::Stop) => None,
+        (false, _) => Some(item.pay_address.clone().try_into()?),
+    };
+    Self { pay_address, extra_data: RpcExtraData::from_iter(item.extra_data.bytes()), command }
+});
+try_from!(&protowire::NotifyBlockTemplateResponseMessage, RpcResult<karlsen_rpc_core::NotifyBlockTemplateResponse>);
+
 // ~~~
 
 try_from!(&protowire::GetCurrentNetworkRequestMessage, karlsen_rpc_core::GetCurrentNetworkRequest);
@@ -939,6 +996,57 @@ try_from!(item: &protowire::GetUtxoReturnAddressResponseMessage, RpcResult<karls
     Self { return_address: Address::try_from(item.return_address.clone())? }
 });
 
+try_from!(item: &protowire::GetTransactionRequestMessage, karlsen_rpc_core::GetTransactionRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
+});
+try_from!(item: &protowire::GetTransactionResponseMessage, RpcResult<karlsen_rpc_core::GetTransactionResponse>, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetTransactionResponse".to_string(), "transaction".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &protowire::GetTransactionsByIdsRequestMessage, karlsen_rpc_core::GetTransactionsByIdsRequest, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()? }
+});
+try_from!(item: &protowire::GetTransactionsByIdsResponseMessage, RpcResult<karlsen_rpc_core::GetTransactionsByIdsResponse>, {
+    Self {
+        transactions: item
+            .transactions
+            .iter()
+            .map(karlsen_rpc_core::RpcIndexedTransaction::try_from)
+            .collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::GetAddressHistoryRequestMessage, karlsen_rpc_core::GetAddressHistoryRequest, {
+    Self {
+        address: item.address.as_str().try_into()?,
+        start_daa_score: item.start_daa_score,
+        end_daa_score: item.end_daa_score,
+        cursor: item.cursor.clone(),
+        limit: item.limit,
+    }
+});
+try_from!(item: &protowire::GetAddressHistoryResponseMessage, RpcResult<karlsen_rpc_core::GetAddressHistoryResponse>, {
+    Self {
+        entries: item.entries.iter().map(karlsen_rpc_core::RpcAddressHistoryEntry::try_from).collect::<Result<Vec<_>, _>>()?,
+        next_cursor: item.next_cursor.clone(),
+    }
+});
+
+try_from!(&protowire::CreateBackupRequestMessage, karlsen_rpc_core::CreateBackupRequest);
+try_from!(item: &protowire::CreateBackupResponseMessage, RpcResult<karlsen_rpc_core::CreateBackupResponse>, {
+    Self {
+        path: item.path.clone(),
+        sink: RpcHash::from_str(&item.sink)?,
+        pruning_point: RpcHash::from_str(&item.pruning_point)?,
+    }
+});
+
 try_from!(&protowire::PingRequestMessage, karlsen_rpc_core::PingRequest);
 try_from!(&protowire::PingResponseMessage, RpcResult<karlsen_rpc_core::PingResponse>);
 
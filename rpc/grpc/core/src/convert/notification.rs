@@ -1,5 +1,6 @@
 use crate::protowire::{
-    karlsend_response::Payload, BlockAddedNotificationMessage, KarlsendResponse, NewBlockTemplateNotificationMessage, RpcNotifyCommand,
+    karlsend_response::Payload, BlockAddedNotificationMessage, BlockTemplateNotificationMessage, KarlsendResponse,
+    NewBlockTemplateNotificationMessage, RpcNotifyCommand,
 };
 use crate::protowire::{
     FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
@@ -34,6 +35,7 @@ from!(item: &karlsen_rpc_core::Notification, Payload, {
         Notification::PruningPointUtxoSetOverride(ref notification) => {
             Payload::PruningPointUtxoSetOverrideNotification(notification.into())
         }
+        Notification::BlockTemplate(ref notification) => Payload::BlockTemplateNotification(notification.into()),
     }
 });
 
@@ -41,6 +43,10 @@ from!(item: &karlsen_rpc_core::BlockAddedNotification, BlockAddedNotificationMes
 
 from!(&karlsen_rpc_core::NewBlockTemplateNotification, NewBlockTemplateNotificationMessage);
 
+from!(item: &karlsen_rpc_core::BlockTemplateNotification, BlockTemplateNotificationMessage, {
+    Self { block: item.block.as_ref().map(|block| (&**block).into()), is_synced: item.is_synced }
+});
+
 from!(item: &karlsen_rpc_core::VirtualChainChangedNotification, VirtualChainChangedNotificationMessage, {
     Self {
         removed_chain_block_hashes: item.removed_chain_block_hashes.iter().map(|x| x.to_string()).collect(),
@@ -117,6 +123,7 @@ try_from!(item: &Payload, karlsen_rpc_core::Notification, {
         Payload::PruningPointUtxoSetOverrideNotification(ref notification) => {
             Notification::PruningPointUtxoSetOverride(notification.try_into()?)
         }
+        Payload::BlockTemplateNotification(ref notification) => Notification::BlockTemplate(notification.try_into()?),
         _ => Err(RpcError::UnsupportedFeature)?,
     }
 });
@@ -134,6 +141,19 @@ try_from!(item: &BlockAddedNotificationMessage, karlsen_rpc_core::BlockAddedNoti
 
 try_from!(&NewBlockTemplateNotificationMessage, karlsen_rpc_core::NewBlockTemplateNotification);
 
+try_from!(item: &BlockTemplateNotificationMessage, karlsen_rpc_core::BlockTemplateNotification, {
+    Self {
+        block: Some(Arc::new(
+            item.block
+                .as_ref()
+                .ok_or_else(|| RpcError::MissingRpcFieldError("BlockTemplateNotificationMessage".to_string(), "block".to_string()))?
+                .try_into()?,
+        )),
+        is_synced: item.is_synced,
+        source: None,
+    }
+});
+
 try_from!(item: &VirtualChainChangedNotificationMessage, karlsen_rpc_core::VirtualChainChangedNotification, {
     Self {
         removed_chain_block_hashes: Arc::new(
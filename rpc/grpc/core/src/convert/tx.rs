@@ -90,6 +90,17 @@ from!(item: &karlsen_rpc_core::RpcUtxosByAddressesEntry, protowire::RpcUtxosByAd
     }
 });
 
+from!(item: &karlsen_rpc_core::RpcIndexedTransaction, protowire::RpcIndexedTransaction, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        transaction: item.transaction.as_ref().map(|x| x.into()),
+        block_hash: item.block_hash.to_string(),
+        index_within_block: item.index_within_block,
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        accepting_block_daa_score: item.accepting_block_daa_score,
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -205,3 +216,14 @@ try_from!(item: &protowire::RpcUtxosByAddressesEntry, karlsen_rpc_core::RpcUtxos
             .try_into()?,
     }
 });
+
+try_from!(item: &protowire::RpcIndexedTransaction, karlsen_rpc_core::RpcIndexedTransaction, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        transaction: item.transaction.as_ref().map(karlsen_rpc_core::RpcTransaction::try_from).transpose()?,
+        block_hash: RpcHash::from_str(&item.block_hash)?,
+        index_within_block: item.index_within_block,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+        accepting_block_daa_score: item.accepting_block_daa_score,
+    }
+});
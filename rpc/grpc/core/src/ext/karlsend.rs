@@ -2,9 +2,9 @@ use karlsen_notify::{scope::Scope, subscription::Command};
 
 use crate::protowire::{
     karlsend_request, karlsend_response, KarlsendRequest, KarlsendResponse, NotifyBlockAddedRequestMessage,
-    NotifyFinalityConflictRequestMessage, NotifyNewBlockTemplateRequestMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage, NotifyVirtualChainChangedRequestMessage,
-    NotifyVirtualDaaScoreChangedRequestMessage,
+    NotifyBlockTemplateRequestMessage, NotifyFinalityConflictRequestMessage, NotifyNewBlockTemplateRequestMessage,
+    NotifyPruningPointUtxoSetOverrideRequestMessage, NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage,
+    NotifyVirtualChainChangedRequestMessage, NotifyVirtualDaaScoreChangedRequestMessage,
 };
 
 impl KarlsendRequest {
@@ -64,6 +64,13 @@ impl karlsend_request::Payload {
                     command: command.into(),
                 })
             }
+            Scope::BlockTemplate(ref scope) => {
+                karlsend_request::Payload::NotifyBlockTemplateRequest(NotifyBlockTemplateRequestMessage {
+                    pay_address: scope.pay_address.as_ref().map(|x| x.into()).unwrap_or_default(),
+                    extra_data: String::from_utf8_lossy(&scope.extra_data).into_owned(),
+                    command: command.into(),
+                })
+            }
         }
     }
 
@@ -79,6 +86,7 @@ impl karlsend_request::Payload {
                 | Payload::NotifyVirtualDaaScoreChangedRequest(_)
                 | Payload::NotifyPruningPointUtxoSetOverrideRequest(_)
                 | Payload::NotifyNewBlockTemplateRequest(_)
+                | Payload::NotifyBlockTemplateRequest(_)
                 | Payload::StopNotifyingUtxosChangedRequest(_)
                 | Payload::StopNotifyingPruningPointUtxoSetOverrideRequest(_)
         )
@@ -108,6 +116,7 @@ impl karlsend_response::Payload {
             Payload::VirtualDaaScoreChangedNotification(_) => true,
             Payload::PruningPointUtxoSetOverrideNotification(_) => true,
             Payload::NewBlockTemplateNotification(_) => true,
+            Payload::BlockTemplateNotification(_) => true,
             _ => false,
         }
     }
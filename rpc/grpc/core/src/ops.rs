@@ -1,5 +1,5 @@
 use crate::protowire::{karlsend_request::Payload as RequestPayload, karlsend_response::Payload as ResponsePayload, *};
-use karlsen_rpc_core::RpcError;
+use karlsen_rpc_core::{api::ops::RpcApiOps, RpcError};
 use workflow_core::enums::Describe;
 
 macro_rules! payload_type_enum {
@@ -88,6 +88,10 @@ pub enum KarlsendPayloadOps {
     GetFeeEstimateExperimental,
     GetCurrentBlockColor,
     GetUtxoReturnAddress,
+    GetTransaction,
+    GetTransactionsByIds,
+    GetAddressHistory,
+    CreateBackup,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -98,6 +102,7 @@ pub enum KarlsendPayloadOps {
     NotifyPruningPointUtxoSetOverride,
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
+    NotifyBlockTemplate,
 
     // Legacy stop subscription commands
     StopNotifyingUtxosChanged,
@@ -108,3 +113,70 @@ pub enum KarlsendPayloadOps {
     // The conversion from a notification ResponsePayload into KarlsendPayloadOps fails.
 }
 }
+
+impl From<KarlsendPayloadOps> for RpcApiOps {
+    fn from(op: KarlsendPayloadOps) -> Self {
+        match op {
+            KarlsendPayloadOps::SubmitBlock => RpcApiOps::SubmitBlock,
+            KarlsendPayloadOps::GetBlockTemplate => RpcApiOps::GetBlockTemplate,
+            KarlsendPayloadOps::GetCurrentNetwork => RpcApiOps::GetCurrentNetwork,
+            KarlsendPayloadOps::GetBlock => RpcApiOps::GetBlock,
+            KarlsendPayloadOps::GetBlocks => RpcApiOps::GetBlocks,
+            KarlsendPayloadOps::GetInfo => RpcApiOps::GetInfo,
+            KarlsendPayloadOps::Shutdown => RpcApiOps::Shutdown,
+            KarlsendPayloadOps::GetPeerAddresses => RpcApiOps::GetPeerAddresses,
+            KarlsendPayloadOps::GetSink => RpcApiOps::GetSink,
+            KarlsendPayloadOps::GetMempoolEntry => RpcApiOps::GetMempoolEntry,
+            KarlsendPayloadOps::GetMempoolEntries => RpcApiOps::GetMempoolEntries,
+            KarlsendPayloadOps::GetConnectedPeerInfo => RpcApiOps::GetConnectedPeerInfo,
+            KarlsendPayloadOps::AddPeer => RpcApiOps::AddPeer,
+            KarlsendPayloadOps::SubmitTransaction => RpcApiOps::SubmitTransaction,
+            KarlsendPayloadOps::SubmitTransactionReplacement => RpcApiOps::SubmitTransactionReplacement,
+            KarlsendPayloadOps::GetSubnetwork => RpcApiOps::GetSubnetwork,
+            KarlsendPayloadOps::GetVirtualChainFromBlock => RpcApiOps::GetVirtualChainFromBlock,
+            KarlsendPayloadOps::GetBlockCount => RpcApiOps::GetBlockCount,
+            KarlsendPayloadOps::GetBlockDagInfo => RpcApiOps::GetBlockDagInfo,
+            KarlsendPayloadOps::ResolveFinalityConflict => RpcApiOps::ResolveFinalityConflict,
+            KarlsendPayloadOps::GetHeaders => RpcApiOps::GetHeaders,
+            KarlsendPayloadOps::GetUtxosByAddresses => RpcApiOps::GetUtxosByAddresses,
+            KarlsendPayloadOps::GetBalanceByAddress => RpcApiOps::GetBalanceByAddress,
+            KarlsendPayloadOps::GetBalancesByAddresses => RpcApiOps::GetBalancesByAddresses,
+            KarlsendPayloadOps::GetSinkBlueScore => RpcApiOps::GetSinkBlueScore,
+            KarlsendPayloadOps::Ban => RpcApiOps::Ban,
+            KarlsendPayloadOps::Unban => RpcApiOps::Unban,
+            KarlsendPayloadOps::EstimateNetworkHashesPerSecond => RpcApiOps::EstimateNetworkHashesPerSecond,
+            KarlsendPayloadOps::GetMempoolEntriesByAddresses => RpcApiOps::GetMempoolEntriesByAddresses,
+            KarlsendPayloadOps::GetCoinSupply => RpcApiOps::GetCoinSupply,
+            KarlsendPayloadOps::Ping => RpcApiOps::Ping,
+            KarlsendPayloadOps::GetMetrics => RpcApiOps::GetMetrics,
+            KarlsendPayloadOps::GetConnections => RpcApiOps::GetConnections,
+            KarlsendPayloadOps::GetSystemInfo => RpcApiOps::GetSystemInfo,
+            KarlsendPayloadOps::GetServerInfo => RpcApiOps::GetServerInfo,
+            KarlsendPayloadOps::GetSyncStatus => RpcApiOps::GetSyncStatus,
+            KarlsendPayloadOps::GetDaaScoreTimestampEstimate => RpcApiOps::GetDaaScoreTimestampEstimate,
+            KarlsendPayloadOps::GetFeeEstimate => RpcApiOps::GetFeeEstimate,
+            KarlsendPayloadOps::GetFeeEstimateExperimental => RpcApiOps::GetFeeEstimateExperimental,
+            KarlsendPayloadOps::GetCurrentBlockColor => RpcApiOps::GetCurrentBlockColor,
+            KarlsendPayloadOps::GetUtxoReturnAddress => RpcApiOps::GetUtxoReturnAddress,
+            KarlsendPayloadOps::GetTransaction => RpcApiOps::GetTransaction,
+            KarlsendPayloadOps::GetTransactionsByIds => RpcApiOps::GetTransactionsByIds,
+            KarlsendPayloadOps::GetAddressHistory => RpcApiOps::GetAddressHistory,
+            KarlsendPayloadOps::CreateBackup => RpcApiOps::CreateBackup,
+
+            // Subscription commands
+            KarlsendPayloadOps::NotifyBlockAdded => RpcApiOps::NotifyBlockAdded,
+            KarlsendPayloadOps::NotifyNewBlockTemplate => RpcApiOps::NotifyNewBlockTemplate,
+            KarlsendPayloadOps::NotifyFinalityConflict => RpcApiOps::NotifyFinalityConflict,
+            KarlsendPayloadOps::NotifyUtxosChanged => RpcApiOps::NotifyUtxosChanged,
+            KarlsendPayloadOps::NotifySinkBlueScoreChanged => RpcApiOps::NotifySinkBlueScoreChanged,
+            KarlsendPayloadOps::NotifyPruningPointUtxoSetOverride => RpcApiOps::NotifyPruningPointUtxoSetOverride,
+            KarlsendPayloadOps::NotifyVirtualDaaScoreChanged => RpcApiOps::NotifyVirtualDaaScoreChanged,
+            KarlsendPayloadOps::NotifyVirtualChainChanged => RpcApiOps::NotifyVirtualChainChanged,
+            KarlsendPayloadOps::NotifyBlockTemplate => RpcApiOps::NotifyBlockTemplate,
+
+            // Legacy stop subscription commands
+            KarlsendPayloadOps::StopNotifyingUtxosChanged => RpcApiOps::NotifyUtxosChanged,
+            KarlsendPayloadOps::StopNotifyingPruningPointUtxoSetOverride => RpcApiOps::NotifyPruningPointUtxoSetOverride,
+        }
+    }
+}
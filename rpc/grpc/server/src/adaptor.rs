@@ -1,7 +1,11 @@
 use crate::{connection_handler::ConnectionHandler, manager::Manager};
 use karlsen_core::debug;
 use karlsen_notify::{notifier::Notifier, subscription::context::SubscriptionContext};
-use karlsen_rpc_core::{api::rpc::DynRpcService, notify::connection::ChannelConnection, Notification, RpcResult};
+use karlsen_rpc_core::{
+    api::{auth::RpcAccessPolicy, rate_limit::RpcRateLimiters, rpc::DynRpcService},
+    notify::connection::ChannelConnection,
+    Notification, RpcResult,
+};
 use karlsen_utils::networking::NetAddress;
 use karlsen_utils_tower::counters::TowerConnectionCounters;
 use std::{ops::Deref, sync::Arc};
@@ -40,6 +44,8 @@ impl Adaptor {
         subscription_context: SubscriptionContext,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        access_policy: Arc<RpcAccessPolicy>,
+        rate_limiters: Arc<RpcRateLimiters>,
     ) -> Arc<Self> {
         let (manager_sender, manager_receiver) = mpsc_channel(Self::manager_channel_size());
         let connection_handler = ConnectionHandler::new(
@@ -50,6 +56,8 @@ impl Adaptor {
             subscription_context,
             broadcasters,
             counters,
+            access_policy,
+            rate_limiters,
         );
         let server_termination = connection_handler.serve(serve_address);
         let adaptor = Arc::new(Adaptor::new(Some(server_termination), connection_handler, manager, serve_address));
@@ -21,7 +21,10 @@ use karlsen_notify::{
     listener::{ListenerId, ListenerLifespan},
     notifier::Notifier,
 };
-use karlsen_rpc_core::Notification;
+use karlsen_rpc_core::{
+    api::{auth::RpcRole, rate_limit::RpcRateLimiter},
+    Notification, RpcResult,
+};
 use parking_lot::Mutex;
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -69,6 +72,12 @@ struct Inner {
     /// The socket address of this client
     net_address: SocketAddr,
 
+    /// The role granted to this client, restricting the RPC methods it may call
+    role: Arc<RpcRole>,
+
+    /// The rate limiter shared with the other connections of this client IP, if rates are limited
+    rate_limiter: Option<Arc<RpcRateLimiter>>,
+
     /// The outgoing route for sending messages to this client
     outgoing_route: GrpcSender,
 
@@ -175,7 +184,13 @@ impl Router {
             debug!("GRPC, Route to handler got empty payload, client: {}", connection);
             return Err(GrpcServerError::InvalidRequestPayload);
         }
-        let rpc_op = request.payload.as_ref().unwrap().into();
+        let rpc_op: KarlsendPayloadOps = request.payload.as_ref().unwrap().into();
+        if let Err(err) = connection.role().authorize(rpc_op.into()) {
+            debug!("GRPC, {}, client: {}", err, connection);
+            let response = KarlsendResponse { id: request.id, payload: Some(rpc_op.to_error_response(err)) };
+            connection.enqueue(response).await?;
+            return Ok(());
+        }
         let route = self.get_or_subscribe(connection, rpc_op);
         match route.policy {
             RoutingPolicy::Enqueue => match route.send(request).await {
@@ -215,6 +230,7 @@ impl Display for Connection {
 impl Connection {
     pub(crate) fn new(
         net_address: SocketAddr,
+        role: Arc<RpcRole>,
         server_context: ServerContext,
         interface: Arc<Interface>,
         manager_sender: MpscSender<ManagerEvent>,
@@ -223,10 +239,13 @@ impl Connection {
     ) -> Self {
         let (shutdown_sender, mut shutdown_receiver) = oneshot_channel();
         let mut router = Router::new(server_context.clone(), interface.clone());
+        let rate_limiter = server_context.rate_limiters.get(net_address.ip());
         let connection = Self {
             inner: Arc::new(Inner {
                 connection_id: Uuid::new_v4(),
                 net_address,
+                role,
+                rate_limiter,
                 outgoing_route,
                 manager_sender,
                 server_context,
@@ -314,6 +333,18 @@ impl Connection {
         self.inner.connection_id
     }
 
+    pub fn role(&self) -> &RpcRole {
+        &self.inner.role
+    }
+
+    /// Charges the client `cost` units, failing if it exceeds its rate limit
+    pub fn rate_limit(&self, cost: u32) -> RpcResult<()> {
+        match self.inner.rate_limiter {
+            Some(ref rate_limiter) => rate_limiter.try_acquire(cost),
+            None => Ok(()),
+        }
+    }
+
     pub fn notifier(&self) -> Arc<GrpcNotifier> {
         self.inner.server_context.notifier.clone()
     }
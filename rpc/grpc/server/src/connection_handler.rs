@@ -22,9 +22,9 @@ use karlsen_notify::{
     subscription::{context::SubscriptionContext, MutationPolicies, UtxosChangedMutationPolicy},
 };
 use karlsen_rpc_core::{
-    api::rpc::DynRpcService,
+    api::{auth::RpcAccessPolicy, rate_limit::RpcRateLimiters, rpc::DynRpcService},
     notify::{channel::NotificationChannel, connection::ChannelConnection},
-    Notification, RpcResult,
+    Notification, RpcError, RpcResult,
 };
 use karlsen_utils::networking::NetAddress;
 use karlsen_utils_tower::{
@@ -54,11 +54,20 @@ pub struct ServerContext {
     pub core_service: DynRpcService,
     /// The notifier relaying RPC core notifications to connections
     pub notifier: Arc<Notifier<Notification, Connection>>,
+    /// The roles granted to the clients according to their token
+    pub access_policy: Arc<RpcAccessPolicy>,
+    /// The rate limiters of the clients according to their IP address
+    pub rate_limiters: Arc<RpcRateLimiters>,
 }
 
 impl ServerContext {
-    pub fn new(core_service: DynRpcService, notifier: Arc<Notifier<Notification, Connection>>) -> Self {
-        Self { core_service, notifier }
+    pub fn new(
+        core_service: DynRpcService,
+        notifier: Arc<Notifier<Notification, Connection>>,
+        access_policy: Arc<RpcAccessPolicy>,
+        rate_limiters: Arc<RpcRateLimiters>,
+    ) -> Self {
+        Self { core_service, notifier, access_policy, rate_limiters }
     }
 }
 
@@ -80,6 +89,10 @@ pub struct ConnectionHandler {
 
 const GRPC_SERVER: &str = "grpc-server";
 
+/// The request metadata key carrying the client token
+pub const AUTHORIZATION: &str = "authorization";
+const BEARER: &str = "Bearer ";
+
 impl ConnectionHandler {
     pub(crate) fn new(
         network_bps: u64,
@@ -89,6 +102,8 @@ impl ConnectionHandler {
         subscription_context: SubscriptionContext,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        access_policy: Arc<RpcAccessPolicy>,
+        rate_limiters: Arc<RpcRateLimiters>,
     ) -> Self {
         // This notifier UTXOs subscription granularity to rpc-core notifier
         let policies = MutationPolicies::new(UtxosChangedMutationPolicy::AddressSet);
@@ -114,7 +129,7 @@ impl ConnectionHandler {
             broadcasters,
             policies,
         ));
-        let server_context = ServerContext::new(core_service, notifier);
+        let server_context = ServerContext::new(core_service, notifier, access_policy, rate_limiters);
         let interface = Arc::new(Factory::new_interface(server_context.clone(), network_bps));
         let running = Default::default();
 
@@ -256,6 +271,22 @@ impl Rpc for ConnectionHandler {
 
         debug!("GRPC, Incoming message stream from {:?}", remote_address);
 
+        // Grant the connection the role of its bearer token, if any
+        let token = match request.metadata().get(AUTHORIZATION) {
+            Some(value) => Some(
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| value.strip_prefix(BEARER).unwrap_or(value).trim())
+                    .ok_or_else(|| tonic::Status::unauthenticated(RpcError::InvalidAuthToken.to_string()))?,
+            ),
+            None => None,
+        };
+        let role = self.server_context.access_policy.authenticate(token).map_err(|err| {
+            debug!("GRPC, Refusing incoming message stream from {:?} - {}", remote_address, err);
+            tonic::Status::unauthenticated(err.to_string())
+        })?;
+
         // Build the in/out pipes
         let (outgoing_route, outgoing_receiver) = mpsc_channel(Self::outgoing_route_channel_size());
         let incoming_stream = request.into_inner();
@@ -263,6 +294,7 @@ impl Rpc for ConnectionHandler {
         // Build the connection object
         let connection = Connection::new(
             remote_address,
+            role,
             self.server_context(),
             self.interface(),
             self.manager_sender(),
@@ -14,7 +14,7 @@ use crate::{
 use karlsen_grpc_core::protowire::{karlsend_request::Payload, *};
 use karlsen_grpc_core::{ops::KarlsendPayloadOps, protowire::NotifyFinalityConflictResponseMessage};
 use karlsen_notify::{scope::FinalityConflictResolvedScope, subscriber::SubscriptionManager};
-use karlsen_rpc_core::{SubmitBlockRejectReason, SubmitBlockReport, SubmitBlockResponse};
+use karlsen_rpc_core::{api::rate_limit::RequestCost, SubmitBlockRejectReason, SubmitBlockReport, SubmitBlockResponse};
 use karlsen_rpc_macros::build_grpc_server_interface;
 
 pub struct Factory {}
@@ -82,8 +82,13 @@ impl Factory {
                 GetFeeEstimateExperimental,
                 GetCurrentBlockColor,
                 GetUtxoReturnAddress,
+                GetTransaction,
+                GetTransactionsByIds,
+                GetAddressHistory,
+                CreateBackup,
                 NotifyBlockAdded,
                 NotifyNewBlockTemplate,
+                NotifyBlockTemplate,
                 NotifyFinalityConflict,
                 NotifyUtxosChanged,
                 NotifySinkBlueScoreChanged,
@@ -5,6 +5,7 @@ use karlsen_core::{
     task::service::{AsyncService, AsyncServiceFuture},
     trace, warn,
 };
+use karlsen_rpc_core::api::{auth::RpcAccessPolicy, rate_limit::RpcRateLimiters};
 use karlsen_rpc_service::service::RpcCoreService;
 use karlsen_utils::{networking::NetAddress, triggers::SingleTrigger};
 use karlsen_utils_tower::counters::TowerConnectionCounters;
@@ -20,6 +21,8 @@ pub struct GrpcService {
     started: SingleTrigger,
     shutdown: SingleTrigger,
     counters: Arc<TowerConnectionCounters>,
+    access_policy: Arc<RpcAccessPolicy>,
+    rate_limiters: Arc<RpcRateLimiters>,
 }
 
 impl GrpcService {
@@ -32,6 +35,8 @@ impl GrpcService {
         rpc_max_clients: usize,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        access_policy: Arc<RpcAccessPolicy>,
+        rate_limiters: Arc<RpcRateLimiters>,
     ) -> Self {
         Self {
             net_address: address,
@@ -42,6 +47,8 @@ impl GrpcService {
             started: Default::default(),
             shutdown: Default::default(),
             counters,
+            access_policy,
+            rate_limiters,
         }
     }
 
@@ -71,6 +78,8 @@ impl AsyncService for GrpcService {
             self.core_service.subscription_context(),
             self.broadcasters,
             self.counters.clone(),
+            self.access_policy.clone(),
+            self.rate_limiters.clone(),
         );
 
         // Signal the server was started
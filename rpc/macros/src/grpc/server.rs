@@ -68,12 +68,15 @@ impl ToTokens for RpcTable {
                     targets.push(quote! {
                         #payload_ops::#handler => {
                             let method: Method<#server_ctx_type, #connection_ctx_type, #karlsend_request_type, #karlsend_response_type> =
-                            Method::new(|server_ctx: #server_ctx_type, _: #connection_ctx_type, request: #karlsend_request_type| {
+                            Method::new(|server_ctx: #server_ctx_type, connection: #connection_ctx_type, request: #karlsend_request_type| {
                                 Box::pin(async move {
                                     let mut response: #karlsend_response_type = match request.payload {
-                                        Some(Payload::#request_type(ref request)) => match request.try_into() {
+                                        Some(Payload::#request_type(ref request)) => match karlsen_rpc_core::#request_type::try_from(request) {
                                             // TODO: RPC-CONNECTION
-                                            Ok(request) => server_ctx.core_service.#fn_call(None,request).await.into(),
+                                            Ok(request) => match connection.rate_limit(request.cost()) {
+                                                Ok(()) => server_ctx.core_service.#fn_call(None,request).await.into(),
+                                                Err(err) => #response_message_type::from(err).into(),
+                                            },
                                             Err(err) => #response_message_type::from(err).into(),
                                         },
                                         _ => {
@@ -100,11 +103,16 @@ impl ToTokens for RpcTable {
                                                 Ok(request) => {
                                                     let listener_id = connection.get_or_register_listener_id()?;
                                                     let command = request.command;
-                                                    let result = server_ctx
-                                                        .notifier
-                                                        .clone()
-                                                        .execute_subscribe_command(listener_id, request.into(), command)
-                                                        .await;
+                                                    let scope: karlsen_notify::scope::Scope = request.into();
+                                                    let result = match connection.rate_limit(scope.cost()) {
+                                                        Ok(()) => server_ctx
+                                                            .notifier
+                                                            .clone()
+                                                            .execute_subscribe_command(listener_id, scope, command)
+                                                            .await
+                                                            .map_err(Into::into),
+                                                        Err(err) => Err(err),
+                                                    };
                                                     #response_message_type::from(result).into()
                                                 }
                                                 Err(err) => #response_message_type::from(err).into(),
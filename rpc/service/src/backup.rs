@@ -0,0 +1,121 @@
+//!
+//! Hot backups of the node databases.
+//!
+//! A backup is a directory laid out like the data directory of the node, holding RocksDB checkpoints of the
+//! active consensus DB, the multi-consensus management (meta) DB and, if enabled, the UTXO index DB, along with
+//! a [`BackupManifest`]. The manifest is written last, so a backup directory without one is incomplete.
+//!
+
+use karlsen_consensus_core::{config::Config, Hash};
+use karlsen_consensusmanager::{spawn_blocking, ConsensusManager};
+use karlsen_core::{info, time::unix_now, warn};
+use karlsen_rpc_core::{RpcError, RpcResult};
+use karlsen_utxoindex::api::UtxoIndexProxy;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Name of the manifest file of a backup
+pub const BACKUP_MANIFEST_FILE: &str = "manifest.json";
+
+const BACKUP_MANIFEST_VERSION: u32 = 1;
+
+// Same names as the databases within the data directory, so a backup can be restored by copying it over
+const CONSENSUS_DB: &str = "consensus";
+const META_DB: &str = "meta";
+const UTXOINDEX_DB: &str = "utxoindex";
+
+/// Describes the content of a backup
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    pub version: u32,
+    pub network: String,
+    /// Unix timestamp in milliseconds
+    pub created_at: u64,
+    /// The sink when the consensus checkpoint was taken. Blocks added concurrently might be included as well
+    pub sink: Hash,
+    pub pruning_point: Hash,
+    pub is_archival: bool,
+    /// Paths of the databases, relative to the backup directory
+    pub databases: Vec<String>,
+}
+
+/// Creates a new backup within `backup_root` and returns its path along with its manifest.
+///
+/// The UTXO index is checkpointed right after the consensus so it might be slightly ahead. The index
+/// notices the mismatch of its tips against the restored consensus and resyncs on startup.
+pub async fn create_backup(
+    consensus_manager: Arc<ConsensusManager>,
+    utxoindex: Option<UtxoIndexProxy>,
+    config: &Config,
+    backup_root: &Path,
+) -> RpcResult<(PathBuf, BackupManifest)> {
+    let created_at = unix_now();
+    let backup_dir = backup_root.join(format!("backup-{created_at}"));
+    if backup_dir.exists() {
+        return Err(RpcError::BackupError(format!("{} already exists", backup_dir.display())));
+    }
+    info!("Creating a backup of the node databases in {}", backup_dir.display());
+    let result = write_backup(consensus_manager, utxoindex, config, &backup_dir, created_at).await;
+    match result {
+        Ok(manifest) => {
+            info!("Backup {} created (sink {}, pruning point {})", backup_dir.display(), manifest.sink, manifest.pruning_point);
+            Ok((backup_dir, manifest))
+        }
+        Err(err) => {
+            warn!("Backup {} failed: {}", backup_dir.display(), err);
+            if let Err(err) = fs::remove_dir_all(&backup_dir) {
+                warn!("Failed removing the incomplete backup {}: {}", backup_dir.display(), err);
+            }
+            Err(err)
+        }
+    }
+}
+
+async fn write_backup(
+    consensus_manager: Arc<ConsensusManager>,
+    utxoindex: Option<UtxoIndexProxy>,
+    config: &Config,
+    backup_dir: &Path,
+    created_at: u64,
+) -> RpcResult<BackupManifest> {
+    let consensus_root = backup_dir.join(CONSENSUS_DB);
+    fs::create_dir_all(&consensus_root).map_err(backup_error)?;
+
+    let meta_dir = backup_dir.join(META_DB);
+    let checkpoint = spawn_blocking(move || consensus_manager.create_checkpoint(&consensus_root, &meta_dir))
+        .await
+        .unwrap()
+        .map_err(backup_error)?;
+
+    let mut databases = vec![META_DB.to_owned(), relative_path(backup_dir, &checkpoint.path)];
+    if let Some(utxoindex) = utxoindex {
+        utxoindex.create_checkpoint(backup_dir.join(UTXOINDEX_DB)).await.map_err(backup_error)?;
+        databases.push(UTXOINDEX_DB.to_owned());
+    }
+
+    let manifest = BackupManifest {
+        version: BACKUP_MANIFEST_VERSION,
+        network: config.params.net.to_string(),
+        created_at,
+        sink: checkpoint.sink,
+        pruning_point: checkpoint.pruning_point,
+        is_archival: config.is_archival,
+        databases,
+    };
+    let json = serde_json::to_vec_pretty(&manifest).map_err(backup_error)?;
+    fs::write(backup_dir.join(BACKUP_MANIFEST_FILE), json).map_err(backup_error)?;
+    Ok(manifest)
+}
+
+fn relative_path(base: &Path, path: &Path) -> String {
+    path.strip_prefix(base).unwrap_or(path).to_string_lossy().into_owned()
+}
+
+fn backup_error(err: impl ToString) -> RpcError {
+    RpcError::BackupError(err.to_string())
+}
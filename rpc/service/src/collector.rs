@@ -1,6 +1,8 @@
-use crate::converter::{consensus::ConsensusConverter, index::IndexConverter};
+use crate::converter::{block_template::BlockTemplateConverter, consensus::ConsensusConverter, index::IndexConverter};
 use karlsen_notify::collector::CollectorFrom;
 
 pub(crate) type CollectorFromConsensus = CollectorFrom<ConsensusConverter>;
 
+pub(crate) type CollectorFromBlockTemplate = CollectorFrom<BlockTemplateConverter>;
+
 pub(crate) type CollectorFromIndex = CollectorFrom<IndexConverter>;
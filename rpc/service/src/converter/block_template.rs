@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use karlsen_consensus_core::{
+    coinbase::MinerData,
+    config::Config,
+    daa_score_timestamp::DaaScoreTimestamp,
+    network::NetworkType,
+    tx::{ScriptPublicKey, COINBASE_TRANSACTION_INDEX},
+};
+use karlsen_consensus_notify::notification::Notification as ConsensusNotification;
+use karlsen_consensusmanager::ConsensusManager;
+use karlsen_core::{karlsend_env::version, trace};
+use karlsen_mining::manager::MiningManagerProxy;
+use karlsen_notify::converter::Converter;
+use karlsen_p2p_mining::rule_engine::MiningRuleEngine;
+use karlsen_rpc_core::{BlockTemplateNotification, BlockTemplateSource, Notification, RpcAddress, RpcRawBlock};
+use karlsen_txscript::pay_to_address_script;
+use std::{fmt::Debug, iter::once, sync::Arc};
+
+/// Builds the block templates of BlockTemplate notifications with the same rules as the GetBlockTemplate RPC call
+pub struct MiningBlockTemplateSource {
+    consensus_manager: Arc<ConsensusManager>,
+    mining_manager: MiningManagerProxy,
+    config: Arc<Config>,
+    mining_rule_engine: Arc<MiningRuleEngine>,
+}
+
+impl MiningBlockTemplateSource {
+    pub fn new(
+        consensus_manager: Arc<ConsensusManager>,
+        mining_manager: MiningManagerProxy,
+        config: Arc<Config>,
+        mining_rule_engine: Arc<MiningRuleEngine>,
+    ) -> Self {
+        Self { consensus_manager, mining_manager, config, mining_rule_engine }
+    }
+}
+
+impl BlockTemplateSource for MiningBlockTemplateSource {
+    fn build(&self, pay_address: &RpcAddress, extra_data: &[u8]) -> Option<(RpcRawBlock, bool)> {
+        if *self.config.net == NetworkType::Mainnet && !self.config.enable_mainnet_mining {
+            return None;
+        }
+        if pay_address.prefix != self.config.prefix() {
+            return None;
+        }
+
+        let script_public_key = pay_to_address_script(pay_address);
+        let extra_data = version().as_bytes().iter().chain(once(&(b'/'))).chain(extra_data).cloned().collect::<Vec<_>>();
+        let miner_data = MinerData::new(script_public_key, extra_data);
+        let session = self.consensus_manager.consensus().unguarded_session_blocking();
+        let block_template = match self.mining_manager.get_block_template_blocking(&*session, &miner_data) {
+            Ok(block_template) => block_template,
+            Err(err) => {
+                trace!("Failed to build a block template for {}: {}", pay_address, err);
+                return None;
+            }
+        };
+
+        if block_template.block.transactions[COINBASE_TRANSACTION_INDEX].payload.len() > self.config.max_coinbase_payload_len {
+            return None;
+        }
+
+        let is_synced = self.mining_rule_engine.should_mine(DaaScoreTimestamp {
+            timestamp: block_template.selected_parent_timestamp,
+            daa_score: block_template.selected_parent_daa_score,
+        });
+        Some((block_template.block.into(), is_synced))
+    }
+}
+
+impl Debug for MiningBlockTemplateSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MiningBlockTemplateSource").field("config", &self.config).finish()
+    }
+}
+
+/// Conversion of consensus NewBlockTemplate notifications into rpc_core BlockTemplate notifications
+#[derive(Debug)]
+pub struct BlockTemplateConverter {
+    source: Arc<MiningBlockTemplateSource>,
+}
+
+impl BlockTemplateConverter {
+    pub fn new(source: Arc<MiningBlockTemplateSource>) -> Self {
+        Self { source }
+    }
+}
+
+#[async_trait]
+impl Converter for BlockTemplateConverter {
+    type Incoming = ConsensusNotification;
+    type Outgoing = Notification;
+
+    async fn convert(&self, _incoming: ConsensusNotification) -> Notification {
+        // Build the new template off the async runtime so that the cache is warm when every
+        // subscription gets its own template with only the coinbase modified
+        let session = self.source.consensus_manager.consensus().unguarded_session();
+        let miner_data = MinerData::new(ScriptPublicKey::default(), vec![]);
+        let _ = self.source.mining_manager.clone().get_block_template(&session, miner_data).await;
+        Notification::BlockTemplate(BlockTemplateNotification::with_source(self.source.clone()))
+    }
+}
@@ -1,9 +1,13 @@
 use async_trait::async_trait;
+use karlsen_addresshistory::model::{AddressHistoryEntry, AddressHistoryEntryKind};
 use karlsen_consensus_core::config::Config;
 use karlsen_index_core::indexed_utxos::UtxoSetByScriptPublicKey;
 use karlsen_index_core::notification::{self as index_notify, Notification as IndexNotification};
 use karlsen_notify::converter::Converter;
-use karlsen_rpc_core::{utxo_set_into_rpc, Notification, RpcUtxosByAddressesEntry, UtxosChangedNotification};
+use karlsen_rpc_core::{
+    utxo_set_into_rpc, Notification, RpcAddressHistoryEntry, RpcAddressHistoryEntryKind, RpcUtxosByAddressesEntry,
+    UtxosChangedNotification,
+};
 use std::sync::Arc;
 
 /// Conversion of consensus_core to rpc_core structures
@@ -27,6 +31,24 @@ impl IndexConverter {
     pub fn get_utxos_by_addresses_entries(&self, item: &UtxoSetByScriptPublicKey) -> Vec<RpcUtxosByAddressesEntry> {
         utxo_set_into_rpc(item, Some(self.config.prefix()))
     }
+
+    pub fn get_address_history_entries(&self, entries: Vec<AddressHistoryEntry>) -> Vec<RpcAddressHistoryEntry> {
+        entries
+            .into_iter()
+            .map(|entry| RpcAddressHistoryEntry {
+                transaction_id: entry.transaction_id,
+                kind: match entry.kind {
+                    AddressHistoryEntryKind::Received => RpcAddressHistoryEntryKind::Received,
+                    AddressHistoryEntryKind::Spent => RpcAddressHistoryEntryKind::Spent,
+                },
+                index: entry.index,
+                outpoint: entry.outpoint.into(),
+                amount: entry.amount,
+                accepting_block_hash: entry.accepting_block_hash,
+                accepting_block_daa_score: entry.accepting_block_daa_score,
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
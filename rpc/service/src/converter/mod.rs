@@ -1,3 +1,4 @@
+pub mod block_template;
 pub mod consensus;
 pub mod feerate_estimate;
 pub mod index;
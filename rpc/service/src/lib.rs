@@ -1,3 +1,4 @@
+pub mod backup;
 pub mod collector;
 pub mod converter;
 pub mod service;
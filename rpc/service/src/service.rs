@@ -1,9 +1,16 @@
 //! Core server implementation for ClientAPI
 
-use super::collector::{CollectorFromConsensus, CollectorFromIndex};
+use super::backup;
+use super::collector::{CollectorFromBlockTemplate, CollectorFromConsensus, CollectorFromIndex};
 use crate::converter::feerate_estimate::{FeeEstimateConverter, FeeEstimateVerboseConverter};
-use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, protocol::ProtocolConverter};
+use crate::converter::{
+    block_template::{BlockTemplateConverter, MiningBlockTemplateSource},
+    consensus::ConsensusConverter,
+    index::IndexConverter,
+    protocol::ProtocolConverter,
+};
 use async_trait::async_trait;
+use karlsen_addresshistory::{api::AddressHistoryProxy, model::AddressHistoryCursor};
 use karlsen_consensus_core::api::counters::ProcessingCounters;
 use karlsen_consensus_core::daa_score_timestamp::DaaScoreTimestamp;
 use karlsen_consensus_core::errors::block::RuleError;
@@ -14,6 +21,7 @@ use karlsen_consensus_core::{
     coinbase::MinerData,
     config::Config,
     constants::MAX_SOMPI,
+    header::Header,
     network::NetworkType,
     tx::{Transaction, COINBASE_TRANSACTION_INDEX},
 };
@@ -21,7 +29,7 @@ use karlsen_consensus_notify::{
     notifier::ConsensusNotifier,
     {connection::ConsensusChannelConnection, notification::Notification as ConsensusNotification},
 };
-use karlsen_consensusmanager::ConsensusManager;
+use karlsen_consensusmanager::{ConsensusManager, ConsensusProxy};
 use karlsen_core::time::unix_now;
 use karlsen_core::{
     core::Core,
@@ -49,7 +57,7 @@ use karlsen_notify::{
     events::{EventSwitches, EventType, EVENT_TYPE_ARRAY},
     listener::ListenerId,
     notifier::Notifier,
-    scope::Scope,
+    scope::{NewBlockTemplateScope, Scope},
     subscriber::{Subscriber, SubscriptionManager},
 };
 use karlsen_p2p_flows::flow_context::FlowContext;
@@ -60,22 +68,25 @@ use karlsen_rpc_core::{
     api::{
         connection::DynRpcConnection,
         ops::{RPC_API_REVISION, RPC_API_VERSION},
-        rpc::{RpcApi, MAX_SAFE_WINDOW_SIZE},
+        rpc::{RpcApi, MAX_ADDRESS_HISTORY_ENTRIES, MAX_SAFE_WINDOW_SIZE, MAX_TRANSACTIONS_BY_IDS},
     },
     model::*,
     notify::connection::ChannelConnection,
     Notification, RpcError, RpcResult,
 };
+use karlsen_txindex::{api::TxIndexProxy, model::TxIndexEntry};
 use karlsen_txscript::{extract_script_pub_key_address, pay_to_address_script};
 use karlsen_utils::expiring_cache::ExpiringCache;
+use karlsen_utils::hex::{FromHex, ToHex};
 use karlsen_utils::sysinfo::SystemInfo;
 use karlsen_utils::{channel::Channel, triggers::SingleTrigger};
 use karlsen_utils_tower::counters::TowerConnectionCounters;
 use karlsen_utxoindex::api::UtxoIndexProxy;
 use std::time::Duration;
 use std::{
-    collections::HashMap,
+    collections::{hash_map::Entry, HashMap},
     iter::once,
+    path::PathBuf,
     sync::{atomic::Ordering, Arc},
     vec,
 };
@@ -105,6 +116,8 @@ pub struct RpcCoreService {
     mining_manager: MiningManagerProxy,
     flow_context: Arc<FlowContext>,
     utxoindex: Option<UtxoIndexProxy>,
+    txindex: Option<TxIndexProxy>,
+    addresshistory: Option<AddressHistoryProxy>,
     config: Arc<Config>,
     consensus_converter: Arc<ConsensusConverter>,
     index_converter: Arc<IndexConverter>,
@@ -122,9 +135,12 @@ pub struct RpcCoreService {
     fee_estimate_cache: ExpiringCache<RpcFeeEstimate>,
     fee_estimate_verbose_cache: ExpiringCache<karlsen_mining::errors::MiningManagerResult<GetFeeEstimateExperimentalResponse>>,
     mining_rule_engine: Arc<MiningRuleEngine>,
+    backup_dir: Option<PathBuf>,
+    backup_lock: tokio::sync::Mutex<()>,
 }
 
 const RPC_CORE: &str = "rpc-core";
+const BLOCK_TEMPLATE: &str = "rpc-core-block-template";
 
 impl RpcCoreService {
     pub const IDENT: &'static str = "rpc-core-service";
@@ -138,6 +154,8 @@ impl RpcCoreService {
         flow_context: Arc<FlowContext>,
         subscription_context: SubscriptionContext,
         utxoindex: Option<UtxoIndexProxy>,
+        txindex: Option<TxIndexProxy>,
+        addresshistory: Option<AddressHistoryProxy>,
         config: Arc<Config>,
         core: Arc<Core>,
         processing_counters: Arc<ProcessingCounters>,
@@ -148,6 +166,7 @@ impl RpcCoreService {
         grpc_tower_counters: Arc<TowerConnectionCounters>,
         system_info: SystemInfo,
         mining_rule_engine: Arc<MiningRuleEngine>,
+        backup_dir: Option<PathBuf>,
     ) -> Self {
         // This notifier UTXOs subscription granularity to index-processor or consensus notifier
         let policies = match index_notifier {
@@ -166,16 +185,43 @@ impl RpcCoreService {
         let mut consensus_events: EventSwitches = EVENT_TYPE_ARRAY[..].into();
         consensus_events[EventType::UtxosChanged] = false;
         consensus_events[EventType::PruningPointUtxoSetOverride] = index_notifier.is_none();
+        consensus_events[EventType::BlockTemplate] = false;
         let consensus_converter = Arc::new(ConsensusConverter::new(consensus_manager.clone(), config.clone()));
         let consensus_collector = Arc::new(CollectorFromConsensus::new(
             "rpc-core <= consensus",
             consensus_notify_channel.receiver(),
             consensus_converter.clone(),
         ));
-        let consensus_subscriber =
-            Arc::new(Subscriber::new("rpc-core => consensus", consensus_events, consensus_notifier, consensus_notify_listener_id));
+        let consensus_subscriber = Arc::new(Subscriber::new(
+            "rpc-core => consensus",
+            consensus_events,
+            consensus_notifier.clone(),
+            consensus_notify_listener_id,
+        ));
+
+        // Prepare the block template objects, listening to every consensus NewBlockTemplate notification
+        // so that BlockTemplate subscriptions are served without relying on a NewBlockTemplate subscription
+        let block_template_notify_channel = Channel::<ConsensusNotification>::default();
+        let block_template_notify_listener_id = consensus_notifier.register_new_listener(
+            ConsensusChannelConnection::new(BLOCK_TEMPLATE, block_template_notify_channel.sender(), ChannelType::Closable),
+            ListenerLifespan::Static(Default::default()),
+        );
+        consensus_notifier
+            .try_start_notify(block_template_notify_listener_id, NewBlockTemplateScope::default().into())
+            .expect("the subscription always succeeds");
+        let block_template_source = Arc::new(MiningBlockTemplateSource::new(
+            consensus_manager.clone(),
+            mining_manager.clone(),
+            config.clone(),
+            mining_rule_engine.clone(),
+        ));
+        let block_template_collector = Arc::new(CollectorFromBlockTemplate::new(
+            "rpc-core <= block template",
+            block_template_notify_channel.receiver(),
+            Arc::new(BlockTemplateConverter::new(block_template_source)),
+        ));
 
-        let mut collectors: Vec<DynCollector<Notification>> = vec![consensus_collector];
+        let mut collectors: Vec<DynCollector<Notification>> = vec![consensus_collector, block_template_collector];
         let mut subscribers = vec![consensus_subscriber];
 
         // Prepare index-processor objects if an IndexService is provided
@@ -210,6 +256,8 @@ impl RpcCoreService {
             mining_manager,
             flow_context,
             utxoindex,
+            txindex,
+            addresshistory,
             config,
             consensus_converter,
             index_converter,
@@ -227,6 +275,8 @@ impl RpcCoreService {
             fee_estimate_cache: ExpiringCache::new(Duration::from_millis(500), Duration::from_millis(1000)),
             fee_estimate_verbose_cache: ExpiringCache::new(Duration::from_millis(500), Duration::from_millis(1000)),
             mining_rule_engine,
+            backup_dir,
+            backup_lock: Default::default(),
         }
     }
 
@@ -275,6 +325,41 @@ impl RpcCoreService {
             .unwrap_or_default()
     }
 
+    /// Builds an [`RpcIndexedTransaction`] out of a txindex entry, attaching the transaction itself
+    /// if the body of the containing block is still available.
+    /// Fetches the header and body of a block holding indexed transactions, or `None` if the block body was pruned
+    async fn get_indexed_transactions_block(
+        &self,
+        session: &ConsensusProxy,
+        hash: RpcHash,
+    ) -> Option<(Arc<Header>, Arc<Vec<Transaction>>)> {
+        match (session.async_get_header(hash).await, session.async_get_block_body(hash).await) {
+            (Ok(header), Ok(body)) => Some((header, body)),
+            _ => None,
+        }
+    }
+
+    fn get_indexed_transaction(
+        &self,
+        session: &ConsensusProxy,
+        transaction_id: RpcTransactionId,
+        entry: TxIndexEntry,
+        block: Option<&(Arc<Header>, Arc<Vec<Transaction>>)>,
+    ) -> RpcIndexedTransaction {
+        let transaction = block.and_then(|(header, body)| {
+            body.get(entry.index_within_block as usize)
+                .map(|tx| self.consensus_converter.get_transaction(session, tx, Some(header), true))
+        });
+        RpcIndexedTransaction {
+            transaction_id,
+            transaction,
+            block_hash: entry.block_hash,
+            index_within_block: entry.index_within_block,
+            accepting_block_hash: entry.accepting_block_hash,
+            accepting_block_daa_score: entry.accepting_block_daa_score,
+        }
+    }
+
     fn extract_tx_query(&self, filter_transaction_pool: bool, include_orphan_pool: bool) -> RpcResult<TransactionQuery> {
         match (filter_transaction_pool, include_orphan_pool) {
             (true, true) => Ok(TransactionQuery::OrphansOnly),
@@ -922,6 +1007,102 @@ impl RpcApi for RpcCoreService {
         }
     }
 
+    async fn get_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse> {
+        if !self.config.txindex {
+            return Err(RpcError::NoTxIndex);
+        }
+        let entry = self
+            .txindex
+            .clone()
+            .unwrap()
+            .get_transaction_entry(request.transaction_id)
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?
+            .ok_or(RpcError::TransactionNotFound(request.transaction_id))?;
+        let session = self.consensus_manager.consensus().session().await;
+        let block = self.get_indexed_transactions_block(&session, entry.block_hash).await;
+        Ok(GetTransactionResponse::new(self.get_indexed_transaction(&session, request.transaction_id, entry, block.as_ref())))
+    }
+
+    async fn get_transactions_by_ids_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetTransactionsByIdsRequest,
+    ) -> RpcResult<GetTransactionsByIdsResponse> {
+        if !self.config.txindex {
+            return Err(RpcError::NoTxIndex);
+        }
+        if request.transaction_ids.len() > MAX_TRANSACTIONS_BY_IDS {
+            return Err(RpcError::TransactionIdsExceedingMaximum(request.transaction_ids.len(), MAX_TRANSACTIONS_BY_IDS));
+        }
+        let entries = self
+            .txindex
+            .clone()
+            .unwrap()
+            .get_transaction_entries(request.transaction_ids.clone())
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?;
+        let session = self.consensus_manager.consensus().session().await;
+        // Transactions often share blocks, so each block is fetched only once
+        let mut blocks = HashMap::new();
+        let mut transactions = Vec::with_capacity(entries.len());
+        for (transaction_id, entry) in request.transaction_ids.into_iter().zip(entries) {
+            if let Some(entry) = entry {
+                if let Entry::Vacant(vacant) = blocks.entry(entry.block_hash) {
+                    vacant.insert(self.get_indexed_transactions_block(&session, entry.block_hash).await);
+                }
+                let block = blocks[&entry.block_hash].as_ref();
+                transactions.push(self.get_indexed_transaction(&session, transaction_id, entry, block));
+            }
+        }
+        Ok(GetTransactionsByIdsResponse::new(transactions))
+    }
+
+    async fn get_address_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        if !self.config.addresshistoryindex {
+            return Err(RpcError::NoAddressHistoryIndex);
+        }
+        let cursor = request
+            .cursor
+            .as_deref()
+            .map(|cursor| {
+                Vec::<u8>::from_hex(cursor)
+                    .ok()
+                    .and_then(|bytes| AddressHistoryCursor::try_from(bytes.as_slice()).ok())
+                    .ok_or_else(|| RpcError::InvalidAddressHistoryCursor(cursor.to_string()))
+            })
+            .transpose()?;
+        let limit = match request.limit {
+            0 => MAX_ADDRESS_HISTORY_ENTRIES,
+            limit => limit.min(MAX_ADDRESS_HISTORY_ENTRIES),
+        };
+        let page = self
+            .addresshistory
+            .clone()
+            .unwrap()
+            .get_address_history(
+                pay_to_address_script(&request.address),
+                request.start_daa_score,
+                request.end_daa_score,
+                cursor,
+                limit as usize,
+            )
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?;
+        Ok(GetAddressHistoryResponse::new(
+            self.index_converter.get_address_history_entries(page.entries),
+            page.next_cursor.map(|cursor| cursor.to_bytes().as_slice().to_hex()),
+        ))
+    }
+
     async fn ping_call(&self, _connection: Option<&DynRpcConnection>, _: PingRequest) -> RpcResult<PingResponse> {
         Ok(PingResponse {})
     }
@@ -1073,6 +1254,26 @@ impl RpcApi for RpcCoreService {
         Ok(ShutdownResponse {})
     }
 
+    async fn create_backup_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: CreateBackupRequest,
+    ) -> RpcResult<CreateBackupResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("CreateBackup RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let Some(ref backup_dir) = self.backup_dir else {
+            return Err(RpcError::NoBackupDir);
+        };
+        let Ok(_guard) = self.backup_lock.try_lock() else {
+            return Err(RpcError::BackupError("another backup is in progress".to_string()));
+        };
+        let (path, manifest) =
+            backup::create_backup(self.consensus_manager.clone(), self.utxoindex.clone(), &self.config, backup_dir).await?;
+        Ok(CreateBackupResponse::new(path.display().to_string(), manifest.sink, manifest.pruning_point))
+    }
+
     async fn resolve_finality_conflict_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -1275,6 +1476,16 @@ impl RpcApi for RpcCoreService {
                 warn!("RPC subscription to blanket UtxosChanged called while node in safe RPC mode -- ignoring.");
                 Err(RpcError::UnavailableInSafeMode)
             }
+            Scope::BlockTemplate(ref block_template_scope) => match block_template_scope.pay_address {
+                None => Err(RpcError::MissingBlockTemplatePayAddress),
+                Some(ref pay_address) if pay_address.prefix != self.config.prefix() => {
+                    Err(karlsen_addresses::AddressError::InvalidPrefix(pay_address.prefix.to_string()))?
+                }
+                Some(_) => {
+                    self.notifier.clone().start_notify(id, scope).await?;
+                    Ok(())
+                }
+            },
             _ => {
                 self.notifier.clone().start_notify(id, scope).await?;
                 Ok(())
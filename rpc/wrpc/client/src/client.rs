@@ -48,6 +48,8 @@ struct Inner {
     resolver: Mutex<Option<Resolver>>,
     network_id: Mutex<Option<NetworkId>>,
     node_descriptor: Mutex<Option<Arc<NodeDescriptor>>>,
+    // The token presented to the server on every connection
+    auth_token: Mutex<Option<String>>,
 }
 
 impl Inner {
@@ -76,6 +78,7 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::BlockTemplateNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -119,6 +122,7 @@ impl Inner {
             resolver: Mutex::new(resolver),
             network_id: Mutex::new(network_id),
             node_descriptor: Mutex::new(None),
+            auth_token: Mutex::new(None),
         };
         Ok(client)
     }
@@ -143,6 +147,16 @@ impl Inner {
         Ok(())
     }
 
+    /// Grant the connection the role of a token.
+    async fn authenticate(&self, token: String) -> RpcResult<AuthenticateResponse> {
+        let response: Serializable<AuthenticateResponse> = self
+            .rpc_client
+            .call(RpcApiOps::Authenticate, Serializable(AuthenticateRequest::new(token)))
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(response.into_inner())
+    }
+
     fn ctor_url(&self) -> Option<String> {
         self.ctor_url.lock().unwrap().clone()
     }
@@ -385,6 +399,13 @@ impl KarlsenRpcClient {
         &self.inner.rpc_client
     }
 
+    /// Presents `token` to the server, granting the connection the role of the token on
+    /// servers restricting the RPC methods. The token is presented again on every reconnection.
+    pub async fn authenticate(&self, token: String) -> RpcResult<AuthenticateResponse> {
+        self.inner.auth_token.lock().unwrap().replace(token.clone());
+        self.inner.authenticate(token).await
+    }
+
     pub fn rpc_api(self: &Arc<Self>) -> Arc<dyn RpcApi> {
         self.clone()
     }
@@ -566,6 +587,12 @@ impl KarlsenRpcClient {
                         if let Ok(msg) = msg {
                             match msg {
                                 WrpcCtl::Connect => {
+                                    let auth_token = inner.auth_token.lock().unwrap().clone();
+                                    if let Some(token) = auth_token {
+                                        if let Err(err) = inner.authenticate(token).await {
+                                            log_error!("wRPC authentication error: {err}");
+                                        }
+                                    }
                                     inner.rpc_ctl.signal_open().await.expect("(KarlsenRpcClient) rpc_ctl.signal_open() error");
                                 }
                                 WrpcCtl::Disconnect => {
@@ -644,6 +671,10 @@ impl RpcApi for KarlsenRpcClient {
             GetSyncStatus,
             GetSystemInfo,
             GetUtxoReturnAddress,
+            GetTransaction,
+            GetTransactionsByIds,
+            GetAddressHistory,
+            CreateBackup,
             GetUtxosByAddresses,
             GetVirtualChainFromBlock,
             ResolveFinalityConflict,
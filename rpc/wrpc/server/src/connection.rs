@@ -6,7 +6,11 @@ use karlsen_notify::{
     notification::Notification as NotificationT,
     notifier::Notify,
 };
-use karlsen_rpc_core::{api::ops::RpcApiOps, notify::mode::NotificationMode, Notification};
+use karlsen_rpc_core::{
+    api::{auth::RpcRole, ops::RpcApiOps, rate_limit::RpcRateLimiter},
+    notify::mode::NotificationMode,
+    Notification,
+};
 use std::{
     fmt::{Debug, Display},
     sync::{Arc, Mutex},
@@ -51,6 +55,10 @@ struct ConnectionInner {
     pub grpc_client: Option<Arc<GrpcClient>>,
     // not using an atomic in case an Id will change type in the future...
     pub listener_id: Mutex<Option<ListenerId>>,
+    /// The role granted to the connection, none if it must authenticate first
+    pub role: Mutex<Option<Arc<RpcRole>>>,
+    /// The rate limiter shared with the other connections of the peer IP, if rates are limited
+    pub rate_limiter: Option<Arc<RpcRateLimiter>>,
 }
 
 impl ConnectionInner {
@@ -83,12 +91,20 @@ pub struct Connection {
 }
 
 impl Connection {
-    pub fn new(id: u64, peer: &SocketAddr, messenger: Arc<Messenger>, grpc_client: Option<Arc<GrpcClient>>) -> Connection {
+    pub fn new(
+        id: u64,
+        peer: &SocketAddr,
+        messenger: Arc<Messenger>,
+        grpc_client: Option<Arc<GrpcClient>>,
+        role: Option<Arc<RpcRole>>,
+        rate_limiter: Option<Arc<RpcRateLimiter>>,
+    ) -> Connection {
         // If a GrpcClient is provided, it has to come configured in direct mode
         assert!(grpc_client.is_none() || grpc_client.as_ref().unwrap().notification_mode() == NotificationMode::Direct);
         // Should a gRPC client be provided, no listener_id is required for subscriptions so the listener id is set to default
         let listener_id = Mutex::new(grpc_client.clone().map(|_| ListenerId::default()));
-        Connection { inner: Arc::new(ConnectionInner { id, peer: *peer, messenger, grpc_client, listener_id }) }
+        let role = Mutex::new(role);
+        Connection { inner: Arc::new(ConnectionInner { id, peer: *peer, messenger, grpc_client, listener_id, role, rate_limiter }) }
     }
 
     /// Obtain the connection id
@@ -121,6 +137,18 @@ impl Connection {
         self.inner.listener_id.lock().unwrap().replace(listener_id);
     }
 
+    pub fn role(&self) -> Option<Arc<RpcRole>> {
+        self.inner.role.lock().unwrap().clone()
+    }
+
+    pub fn set_role(&self, role: Arc<RpcRole>) {
+        self.inner.role.lock().unwrap().replace(role);
+    }
+
+    pub fn rate_limiter(&self) -> Option<&Arc<RpcRateLimiter>> {
+        self.inner.rate_limiter.as_ref()
+    }
+
     pub fn peer(&self) -> &SocketAddr {
         &self.inner.peer
     }
@@ -1,6 +1,9 @@
 use crate::{connection::*, server::*};
 use karlsen_notify::scope::Scope;
-use karlsen_rpc_core::{api::ops::RpcApiOps, prelude::*};
+use karlsen_rpc_core::{
+    api::{ops::RpcApiOps, rate_limit::RequestCost},
+    prelude::*,
+};
 use karlsen_rpc_macros::build_wrpc_server_interface;
 use std::sync::Arc;
 use workflow_rpc::server::prelude::*;
@@ -49,6 +52,10 @@ impl Router {
                 GetConnectedPeerInfo,
                 GetDaaScoreTimestampEstimate,
                 GetUtxoReturnAddress,
+                GetTransaction,
+                GetTransactionsByIds,
+                GetAddressHistory,
+                CreateBackup,
                 GetCurrentNetwork,
                 GetDaaScoreTimestampEstimate,
                 GetFeeEstimate,
@@ -82,7 +89,10 @@ impl Router {
             RpcApiOps::Subscribe,
             workflow_rpc::server::Method::new(move |manager: Server, connection: Connection, scope: Serializable<Scope>| {
                 Box::pin(async move {
-                    manager.start_notify(&connection, scope.into_inner()).await.map_err(|err| err.to_string())?;
+                    let scope = scope.into_inner();
+                    manager.authorize(&connection, RpcApiOps::subscription(scope.event_type())).map_err(|err| err.to_string())?;
+                    manager.rate_limit(&connection, scope.cost()).map_err(|err| err.to_string())?;
+                    manager.start_notify(&connection, scope).await.map_err(|err| err.to_string())?;
                     Ok(Serializable(SubscribeResponse::new(connection.id())))
                 })
             }),
@@ -100,6 +110,19 @@ impl Router {
             }),
         );
 
+        interface.method(
+            RpcApiOps::Authenticate,
+            workflow_rpc::server::Method::new(
+                move |manager: Server, connection: Connection, request: Serializable<AuthenticateRequest>| {
+                    Box::pin(async move {
+                        let response =
+                            manager.authenticate(&connection, &request.into_inner().token).map_err(|err| err.to_string())?;
+                        Ok(Serializable(response))
+                    })
+                },
+            ),
+        );
+
         Router { interface: Arc::new(interface), server_context }
     }
 }
@@ -15,9 +15,12 @@ use karlsen_notify::{
     subscription::{MutationPolicies, UtxosChangedMutationPolicy},
 };
 use karlsen_rpc_core::{
-    api::rpc::{DynRpcService, RpcApi},
+    api::{
+        ops::RpcApiOps,
+        rpc::{DynRpcService, RpcApi},
+    },
     notify::{channel::NotificationChannel, connection::ChannelConnection, mode::NotificationMode},
-    Notification, RpcResult,
+    AuthenticateResponse, Notification, RpcError, RpcResult,
 };
 use karlsen_rpc_service::service::RpcCoreService;
 use std::{
@@ -126,6 +129,7 @@ impl Server {
                 true,
                 None,
                 Default::default(),
+                None,
             )
             .await
             .map_err(|e| WebSocketError::Other(e.to_string()))?;
@@ -134,7 +138,14 @@ impl Server {
         } else {
             None
         };
-        let connection = Connection::new(id, peer, messenger, grpc_client);
+        let connection = Connection::new(
+            id,
+            peer,
+            messenger,
+            grpc_client,
+            self.inner.options.access_policy.anonymous(),
+            self.inner.options.rate_limiters.get(peer.ip()),
+        );
         if self.inner.options.grpc_proxy_address.is_some() {
             // log_trace!("starting gRPC");
             connection.grpc_client().start(Some(connection.grpc_client_notify_target())).await;
@@ -176,7 +187,34 @@ impl Server {
         }
     }
 
+    /// Returns an error if the role of the connection does not allow `op`
+    pub fn authorize(&self, connection: &Connection, op: RpcApiOps) -> RpcResult<()> {
+        match connection.role() {
+            Some(role) => role.authorize(op),
+            None => Err(RpcError::Unauthenticated),
+        }
+    }
+
+    /// Charges the connection `cost` units, failing if it exceeds the rate limit of its peer IP
+    pub fn rate_limit(&self, connection: &Connection, cost: u32) -> RpcResult<()> {
+        match connection.rate_limiter() {
+            Some(rate_limiter) => rate_limiter.try_acquire(cost),
+            None => Ok(()),
+        }
+    }
+
+    /// Grants the connection the role of `token`
+    pub fn authenticate(&self, connection: &Connection, token: &str) -> RpcResult<AuthenticateResponse> {
+        let role = self.inner.options.access_policy.authenticate(Some(token))?;
+        let response = AuthenticateResponse { role: role.name().to_owned() };
+        connection.set_role(role);
+        Ok(response)
+    }
+
     pub async fn start_notify(&self, connection: &Connection, scope: Scope) -> RpcResult<()> {
+        if matches!(scope, Scope::BlockTemplate(ref scope) if scope.pay_address.is_none()) {
+            return Err(RpcError::MissingBlockTemplatePayAddress);
+        }
         let listener_id = if let Some(listener_id) = connection.listener_id() {
             listener_id
         } else {
@@ -5,7 +5,7 @@ use karlsen_core::{
     task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
     trace, warn,
 };
-use karlsen_rpc_core::api::ops::RpcApiOps;
+use karlsen_rpc_core::api::{auth::RpcAccessPolicy, ops::RpcApiOps, rate_limit::RpcRateLimiters};
 use karlsen_rpc_service::service::RpcCoreService;
 use karlsen_utils::triggers::SingleTrigger;
 use std::sync::Arc;
@@ -20,11 +20,19 @@ pub struct Options {
     pub listen_address: String,
     pub grpc_proxy_address: Option<String>,
     pub verbose: bool,
+    pub access_policy: Arc<RpcAccessPolicy>,
+    pub rate_limiters: Arc<RpcRateLimiters>,
 }
 
 impl Default for Options {
     fn default() -> Self {
-        Options { listen_address: "127.0.0.1:43110".to_owned(), verbose: false, grpc_proxy_address: None }
+        Options {
+            listen_address: "127.0.0.1:43110".to_owned(),
+            verbose: false,
+            grpc_proxy_address: None,
+            access_policy: Default::default(),
+            rate_limiters: Default::default(),
+        }
     }
 }
 
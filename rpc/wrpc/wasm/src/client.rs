@@ -874,6 +874,34 @@ impl RpcClient {
         }
         Ok(())
     }
+
+    /// Manage subscription for a block template notification event.
+    /// Block template notification event is produced when the block template
+    /// changes, carrying a full template paying to the address of the request.
+    #[wasm_bindgen(js_name = subscribeBlockTemplate)]
+    pub async fn subscribe_block_template(&self, request: IGetBlockTemplateRequest) -> Result<()> {
+        if let Some(listener_id) = self.listener_id() {
+            let request: GetBlockTemplateRequest = request.try_into()?;
+            let scope = BlockTemplateScope::new(request.pay_address, request.extra_data);
+            self.inner.client.start_notify(listener_id, Scope::BlockTemplate(scope)).await?;
+        } else {
+            log_error!("RPC subscribe on a closed connection");
+        }
+        Ok(())
+    }
+
+    /// Manage subscription for a block template notification event.
+    /// Block template notification event is produced when the block template
+    /// changes, carrying a full template paying to the address of the request.
+    #[wasm_bindgen(js_name = unsubscribeBlockTemplate)]
+    pub async fn unsubscribe_block_template(&self) -> Result<()> {
+        if let Some(listener_id) = self.listener_id() {
+            self.inner.client.stop_notify(listener_id, Scope::BlockTemplate(BlockTemplateScope::default())).await?;
+        } else {
+            log_error!("RPC unsubscribe on a closed connection");
+        }
+        Ok(())
+    }
 }
 
 // Build subscribe functions
@@ -1056,6 +1084,18 @@ build_wrpc_wasm_bindgen_interface!(
         /// Returned information: None.
         Unban,
         /// Get UTXO Return Addresses.
-        GetUtxoReturnAddress
+        GetUtxoReturnAddress,
+        /// Retrieves a transaction by id from the node's transaction index.
+        /// Returned information: Indexed transaction.
+        GetTransaction,
+        /// Retrieves a set of transactions by ids from the node's transaction index.
+        /// Returned information: Indexed transactions.
+        GetTransactionsByIds,
+        /// Retrieves a page of the history of an address from the node's address history index.
+        /// Returned information: Address history entries and the cursor of the next page.
+        GetAddressHistory,
+        /// Creates a backup of the node databases within the backup directory of the node.
+        /// Returned information: Backup path, sink and pruning point.
+        CreateBackup
     ]
 );
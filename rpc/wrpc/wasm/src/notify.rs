@@ -25,6 +25,7 @@ export enum RpcEventType {
     VirtualDaaScoreChanged = "virtual-daa-score-changed",
     PruningPointUtxoSetOverride = "pruning-point-utxo-set-override",
     NewBlockTemplate = "new-block-template",
+    BlockTemplate = "block-template",
 }
 
 /**
@@ -40,7 +41,8 @@ export type RpcEventData = IBlockAdded
     | ISinkBlueScoreChanged 
     | IVirtualDaaScoreChanged 
     | IPruningPointUtxoSetOverride 
-    | INewBlockTemplate;
+    | INewBlockTemplate 
+    | IBlockTemplate;
 
 /**
  * RPC notification event data map.
@@ -59,6 +61,7 @@ export type RpcEventMap = {
     "virtual-daa-score-changed" : IVirtualDaaScoreChanged,
     "pruning-point-utxo-set-override" : IPruningPointUtxoSetOverride,
     "new-block-template" : INewBlockTemplate,
+    "block-template" : IBlockTemplate,
 }
 
 /**
@@ -84,6 +87,7 @@ export type RpcEvent = {
  * {@link RpcClient.subscribeSinkBlueScoreChanged},
  * {@link RpcClient.subscribePruningPointUtxoSetOverride},
  * {@link RpcClient.subscribeNewBlockTemplate},
+ * {@link RpcClient.subscribeBlockTemplate},
  * 
  * @category Node RPC
  */
@@ -241,3 +245,19 @@ declare! {
     }
     "#,
 }
+
+declare! {
+    IBlockTemplate,
+    r#"
+    /**
+     * Block template notification event is produced when the block template
+     * changes, carrying a full template built for the subscribed pay address.
+     * 
+     * @category Node RPC
+     */
+    export interface IBlockTemplate {
+        block : IRawBlock;
+        isSynced : boolean;
+    }
+    "#,
+}
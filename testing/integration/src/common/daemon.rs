@@ -39,6 +39,11 @@ impl ClientManager {
     }
 
     pub async fn new_client(&self) -> GrpcClient {
+        self.try_new_client_with_token(None).await.unwrap()
+    }
+
+    /// Connects a client presenting `auth_token` to the node
+    pub async fn try_new_client_with_token(&self, auth_token: Option<String>) -> karlsen_grpc_client::error::Result<GrpcClient> {
         GrpcClient::connect_with_args(
             NotificationMode::Direct,
             format!("grpc://localhost:{}", self.rpc_port),
@@ -48,9 +53,9 @@ impl ClientManager {
             false,
             Some(500_000),
             Default::default(),
+            auth_token,
         )
         .await
-        .unwrap()
     }
 
     pub async fn new_clients(&self, count: usize) -> Vec<GrpcClient> {
@@ -71,6 +76,7 @@ impl ClientManager {
             false,
             Some(500_000),
             Default::default(),
+            None,
         )
         .await
         .unwrap()
@@ -4,19 +4,31 @@ use crate::common::{
     daemon::Daemon,
     utils::{fetch_spendable_utxos, generate_tx, mine_block, wait_for},
 };
-use karlsen_addresses::Address;
+use karlsen_addresses::{Address, Prefix};
 use karlsen_alloc::init_allocator_with_default_settings;
 use karlsen_consensus::params::SIMNET_PARAMS;
 use karlsen_consensus_core::header::Header;
 use karlsen_consensusmanager::ConsensusManager;
 use karlsen_core::{task::runtime::AsyncRuntime, trace};
 use karlsen_grpc_client::GrpcClient;
-use karlsen_notify::scope::{BlockAddedScope, UtxosChangedScope, VirtualDaaScoreChangedScope};
-use karlsen_rpc_core::{api::rpc::RpcApi, Notification, RpcTransactionId};
+use karlsen_notify::{
+    connection::{ChannelConnection, ChannelType},
+    scope::{BlockAddedScope, BlockTemplateScope, UtxosChangedScope, VirtualDaaScoreChangedScope},
+};
+use karlsen_rpc_core::{api::rpc::RpcApi, Notification, RpcError, RpcTransactionId};
 use karlsen_txscript::pay_to_address_script;
+use karlsen_wrpc_client::{client::ConnectOptions, KarlsenRpcClient, WrpcEncoding};
 use karlsend_lib::args::Args;
 use rand::thread_rng;
-use std::{sync::Arc, time::Duration};
+use serde_json::{json, Value};
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, Lines},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpStream,
+    },
+};
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn daemon_sanity_test() {
@@ -381,3 +393,311 @@ async fn daemon_cleaning_test() {
     assert_eq!(async_runtime.strong_count(), 0);
     assert_eq!(core.strong_count(), 0);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_prometheus_test() {
+    init_allocator_with_default_settings();
+    karlsen_core::log::try_init_logger("INFO");
+
+    let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let args = Args {
+        devnet: true,
+        disable_upnp: true,
+        prometheus_listen: Some(format!("127.0.0.1:{port}").try_into().unwrap()),
+        ..Default::default()
+    };
+    let total_fd_limit = 10;
+    let mut karlsend = Daemon::new_random_with_args(args, total_fd_limit);
+    let rpc_client = karlsend.start().await;
+
+    let scrape = |path: &'static str| async move {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        stream.write_all(format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n").as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    };
+
+    let response = scrape("/metrics").await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "unexpected response: {response}");
+    assert!(response.to_lowercase().contains("content-type: application/openmetrics-text"));
+    assert!(response.contains("\n# TYPE karlsen_node_blocks_submitted_count counter\n"));
+    assert!(response.contains("\nkarlsen_node_blocks_submitted_count_total 0\n"));
+    assert!(response.contains("\n# TYPE karlsen_network_virtual_daa_score gauge\n"));
+    assert!(response.contains("\nkarlsen_node_chain_blocks_disqualified_count_total 0\n"));
+    assert!(response.ends_with("\n# EOF\n"));
+
+    let response = scrape("/").await;
+    assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"), "unexpected response: {response}");
+
+    rpc_client.disconnect().await.unwrap();
+    drop(rpc_client);
+    karlsend.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_stratum_test() {
+    init_allocator_with_default_settings();
+    karlsen_core::log::try_init_logger("INFO");
+
+    let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let pay_address = Address::new(Prefix::Simnet, karlsen_addresses::Version::PubKey, &[0; 32]);
+    let args = Args {
+        simnet: true,
+        enable_unsynced_mining: true,
+        disable_upnp: true,
+        stratum_listen: Some(format!("127.0.0.1:{port}").try_into().unwrap()),
+        stratum_pay_address: Some(pay_address.to_string()),
+        ..Default::default()
+    };
+    let total_fd_limit = 10;
+    let mut karlsend = Daemon::new_random_with_args(args, total_fd_limit);
+    let rpc_client = karlsend.start().await;
+
+    /// A minimal Stratum client, keeping the notifications received while waiting for a response
+    struct Miner {
+        lines: Lines<BufReader<OwnedReadHalf>>,
+        writer: OwnedWriteHalf,
+        pending: VecDeque<Value>,
+    }
+
+    impl Miner {
+        async fn next_message(&mut self) -> Value {
+            if let Some(message) = self.pending.pop_front() {
+                return message;
+            }
+            let line = tokio::time::timeout(Duration::from_secs(30), self.lines.next_line()).await.unwrap().unwrap().unwrap();
+            serde_json::from_str(&line).unwrap()
+        }
+
+        async fn call(&mut self, request: Value) -> Value {
+            self.writer.write_all(format!("{request}\n").as_bytes()).await.unwrap();
+            loop {
+                let line = tokio::time::timeout(Duration::from_secs(30), self.lines.next_line()).await.unwrap().unwrap().unwrap();
+                let message: Value = serde_json::from_str(&line).unwrap();
+                if message["id"] == request["id"] {
+                    return message;
+                }
+                self.pending.push_back(message);
+            }
+        }
+    }
+
+    let (reader, writer) = TcpStream::connect(("127.0.0.1", port)).await.unwrap().into_split();
+    let mut miner = Miner { lines: BufReader::new(reader).lines(), writer, pending: VecDeque::new() };
+
+    let response = miner.call(json!({"id": 1, "method": "mining.subscribe", "params": ["test-miner/1.0"]})).await;
+    assert_eq!(response["result"][0], true);
+    let response = miner.call(json!({"id": 2, "method": "mining.authorize", "params": ["worker", "x"]})).await;
+    assert_eq!(response["result"], true);
+    let message = miner.next_message().await;
+    assert_eq!(message["method"], "mining.set_difficulty");
+    let job = miner.next_message().await;
+    assert_eq!(job["method"], "mining.notify");
+    let job_id = job["params"][0].as_str().unwrap().to_string();
+    assert_eq!(job["params"][1].as_array().unwrap().len(), 4);
+
+    // The simnet target is met by about every other nonce, while shares of difficulty 1 are not
+    let mut id = 3;
+    let nonce = loop {
+        let nonce = format!("{id:016x}");
+        let response = miner.call(json!({"id": id, "method": "mining.submit", "params": ["worker", job_id, nonce]})).await;
+        if response["result"] == true {
+            break nonce;
+        }
+        assert_eq!(response["error"][0], 23, "unexpected response: {response}");
+        id += 1;
+    };
+
+    // The new block template yields a new job
+    let new_job = loop {
+        let message = miner.next_message().await;
+        if message["method"] == "mining.notify" {
+            break message;
+        }
+    };
+    assert_ne!(new_job["params"][0], job_id);
+
+    id += 1;
+    let response = miner.call(json!({"id": id, "method": "mining.submit", "params": ["worker", job_id, nonce]})).await;
+    assert_eq!(response["error"][0], 22);
+    id += 1;
+    let response = miner.call(json!({"id": id, "method": "mining.submit", "params": ["worker", "999", nonce]})).await;
+    assert_eq!(response["error"][0], 21);
+
+    // The block pays to the configured address and carries the extranonce of the connection
+    let dag_info = rpc_client.get_block_dag_info().await.unwrap();
+    assert_eq!(dag_info.block_count, 1);
+    let block = rpc_client.get_block(dag_info.sink, true).await.unwrap();
+    let payload = &block.transactions[0].payload;
+    let script = pay_to_address_script(&pay_address);
+    assert!(payload.windows(script.script().len()).any(|window| window == script.script()));
+    assert!(String::from_utf8_lossy(payload).ends_with("/stratum/00000000"));
+
+    rpc_client.disconnect().await.unwrap();
+    drop(rpc_client);
+    karlsend.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_block_template_notification_test() {
+    init_allocator_with_default_settings();
+    karlsen_core::log::try_init_logger("INFO");
+
+    let args = Args { simnet: true, unsafe_rpc: true, enable_unsynced_mining: true, disable_upnp: true, ..Default::default() };
+    let total_fd_limit = 10;
+    let mut karlsend = Daemon::new_random_with_args(args, total_fd_limit);
+    let rpc_client = karlsend.start().await;
+
+    // Subscribe over gRPC and wRPC, each with its own pay address
+    let grpc_address = Address::new(Prefix::Simnet, karlsen_addresses::Version::PubKey, &[1; 32]);
+    let (sender, grpc_receiver) = async_channel::unbounded();
+    rpc_client.start(Some(Arc::new(ChannelNotify::new(sender)))).await;
+    rpc_client.start_notify(Default::default(), BlockTemplateScope::new(grpc_address.clone(), b"grpc".to_vec()).into()).await.unwrap();
+
+    let wrpc_address = Address::new(Prefix::Simnet, karlsen_addresses::Version::PubKey, &[2; 32]);
+    let url = karlsend.args.read().rpclisten_borsh.clone().unwrap().to_address(&karlsend.network.network_type, &WrpcEncoding::Borsh);
+    let wrpc_client = KarlsenRpcClient::new(
+        WrpcEncoding::Borsh,
+        Some(&format!("ws://127.0.0.1:{}", url.normalize(0).port)),
+        None,
+        Some(karlsend.network),
+        None,
+    )
+    .unwrap();
+    wrpc_client.connect(Some(ConnectOptions { block_async_connect: true, ..Default::default() })).await.unwrap();
+    let (sender, wrpc_receiver) = async_channel::unbounded();
+    let listener_id = wrpc_client.register_new_listener(ChannelConnection::new("test", sender, ChannelType::Closable));
+    wrpc_client.start_notify(listener_id, BlockTemplateScope::new(wrpc_address.clone(), b"wrpc".to_vec()).into()).await.unwrap();
+    tokio::time::sleep(Duration::from_secs(1)).await; // Let the wRPC subscription reach the node
+
+    // Mining a block changes the template
+    let template = rpc_client.get_block_template(grpc_address.clone(), vec![]).await.unwrap();
+    rpc_client.submit_block(template.block, false).await.unwrap();
+
+    let receivers = [("gRPC", grpc_receiver, grpc_address, b"grpc"), ("wRPC", wrpc_receiver, wrpc_address, b"wrpc")];
+    for (name, receiver, pay_address, extra_data) in receivers {
+        let notification = match tokio::time::timeout(Duration::from_secs(5), receiver.recv()).await {
+            Ok(Ok(Notification::BlockTemplate(notification))) => notification,
+            Ok(Ok(notification)) => panic!("unexpected {} notification {}", name, notification),
+            Ok(Err(err)) => panic!("the {} notification channel closed: {}", name, err),
+            Err(elapsed) => panic!("expected a {} block template before {}", name, elapsed),
+        };
+
+        // The pushed template is the one GetBlockTemplate returns for the subscribed miner
+        let block = notification.block.expect("the notification carries a block template");
+        let expected = rpc_client.get_block_template(pay_address, extra_data.to_vec()).await.unwrap();
+        assert_eq!(notification.is_synced, expected.is_synced);
+        assert_eq!(block.header.daa_score, expected.block.header.daa_score);
+        assert_eq!(block.transactions[0].payload, expected.block.transactions[0].payload);
+    }
+
+    wrpc_client.disconnect().await.unwrap();
+    rpc_client.disconnect().await.unwrap();
+    drop(rpc_client);
+    karlsend.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_rpc_access_test() {
+    init_allocator_with_default_settings();
+    karlsen_core::log::try_init_logger("INFO");
+
+    let args = Args {
+        simnet: true,
+        unsafe_rpc: true,
+        enable_unsynced_mining: true,
+        disable_upnp: true,
+        rpc_tokens: vec!["miner:m1ner".to_owned(), "admin:adm1n".to_owned()],
+        ..Default::default()
+    };
+    let total_fd_limit = 10;
+    let mut karlsend = Daemon::new_random_with_args(args, total_fd_limit);
+    let rpc_client = karlsend.start().await;
+    let pay_address = Address::new(Prefix::Simnet, karlsen_addresses::Version::PubKey, &[1; 32]);
+    let peer_address = "127.0.0.1:16111".parse().unwrap();
+    let is_access_denied = |result: &dyn std::fmt::Debug| format!("{result:?}").contains("is not allowed for the role");
+
+    // gRPC clients presenting no token are granted the public role
+    assert!(rpc_client.get_info().await.is_ok());
+    let result = rpc_client.get_block_template(pay_address.clone(), vec![]).await;
+    assert!(is_access_denied(&result), "{result:?}");
+    let result = rpc_client.add_peer(peer_address, false).await;
+    assert!(is_access_denied(&result), "{result:?}");
+
+    // gRPC clients presenting an unknown token are refused
+    assert!(karlsend.try_new_client_with_token(Some("wrong".to_owned())).await.is_err());
+
+    let miner_client = karlsend.try_new_client_with_token(Some("m1ner".to_owned())).await.unwrap();
+    assert!(miner_client.get_block_template(pay_address.clone(), vec![]).await.is_ok());
+    let result = miner_client.add_peer(peer_address, false).await;
+    assert!(is_access_denied(&result), "{result:?}");
+
+    let admin_client = karlsend.try_new_client_with_token(Some("adm1n".to_owned())).await.unwrap();
+    assert!(admin_client.add_peer(peer_address, false).await.is_ok());
+
+    // wRPC clients authenticate on the connection
+    let url = karlsend.args.read().rpclisten_borsh.clone().unwrap().to_address(&karlsend.network.network_type, &WrpcEncoding::Borsh);
+    let wrpc_client = KarlsenRpcClient::new(
+        WrpcEncoding::Borsh,
+        Some(&format!("ws://127.0.0.1:{}", url.normalize(0).port)),
+        None,
+        Some(karlsend.network),
+        None,
+    )
+    .unwrap();
+    wrpc_client.connect(Some(ConnectOptions { block_async_connect: true, ..Default::default() })).await.unwrap();
+    assert!(wrpc_client.get_info().await.is_ok());
+    let result = wrpc_client.get_block_template(pay_address.clone(), vec![]).await;
+    assert!(is_access_denied(&result), "{result:?}");
+    assert!(wrpc_client.authenticate("wrong".to_owned()).await.is_err());
+    assert_eq!(wrpc_client.authenticate("m1ner".to_owned()).await.unwrap().role, "miner");
+    assert!(wrpc_client.get_block_template(pay_address.clone(), vec![]).await.is_ok());
+    let result = wrpc_client.add_peer(peer_address, false).await;
+    assert!(is_access_denied(&result), "{result:?}");
+
+    wrpc_client.disconnect().await.unwrap();
+    for client in [miner_client, admin_client] {
+        client.disconnect().await.unwrap();
+    }
+    rpc_client.disconnect().await.unwrap();
+    drop(rpc_client);
+    karlsend.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_rpc_rate_limit_test() {
+    init_allocator_with_default_settings();
+    karlsen_core::log::try_init_logger("INFO");
+
+    let args = Args { simnet: true, utxoindex: true, disable_upnp: true, rpc_rate_limit: 1, rpc_rate_burst: 50, ..Default::default() };
+    let total_fd_limit = 10;
+    let mut karlsend = Daemon::new_random_with_args(args, total_fd_limit);
+    let rpc_client = karlsend.start().await;
+    let addresses = (0..40u8).map(|i| Address::new(Prefix::Simnet, karlsen_addresses::Version::PubKey, &[i; 32])).collect::<Vec<_>>();
+
+    // The request cost grows with the number of addresses
+    assert!(rpc_client.get_utxos_by_addresses(addresses.clone()).await.is_ok());
+    let result = rpc_client.get_utxos_by_addresses(addresses.clone()).await;
+    assert!(matches!(result, Err(RpcError::RateLimited(retry_after)) if retry_after > 0), "{result:?}");
+    assert!(rpc_client.get_info().await.is_ok());
+
+    // Connections from the same IP address share the limit
+    let url = karlsend.args.read().rpclisten_borsh.clone().unwrap().to_address(&karlsend.network.network_type, &WrpcEncoding::Borsh);
+    let wrpc_client = KarlsenRpcClient::new(
+        WrpcEncoding::Borsh,
+        Some(&format!("ws://127.0.0.1:{}", url.normalize(0).port)),
+        None,
+        Some(karlsend.network),
+        None,
+    )
+    .unwrap();
+    wrpc_client.connect(Some(ConnectOptions { block_async_connect: true, ..Default::default() })).await.unwrap();
+    let result = wrpc_client.get_utxos_by_addresses(addresses).await;
+    assert!(matches!(result, Err(RpcError::RateLimited(retry_after)) if retry_after > 0), "{result:?}");
+
+    wrpc_client.disconnect().await.unwrap();
+    rpc_client.disconnect().await.unwrap();
+    drop(rpc_client);
+    karlsend.shutdown();
+}
@@ -1,22 +1,31 @@
 use std::{str::FromStr, sync::Arc, time::Duration};
 
-use crate::common::{client_notify::ChannelNotify, daemon::Daemon};
+use crate::common::{client_notify::ChannelNotify, daemon::Daemon, utils::wait_for};
 use futures_util::future::try_join_all;
 use karlsen_addresses::{Address, Prefix, Version};
 use karlsen_consensus::params::SIMNET_GENESIS;
 use karlsen_consensus_core::{constants::MAX_SOMPI, header::Header, subnets::SubnetworkId, tx::Transaction};
 use karlsen_core::{assert_match, info};
+use karlsen_database::utils::get_karlsen_tempdir;
+use karlsen_grpc_client::GrpcClient;
 use karlsen_grpc_core::ops::KarlsendPayloadOps;
 use karlsen_hashes::Hash;
 use karlsen_notify::{
     connection::{ChannelConnection, ChannelType},
     scope::{
-        BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
+        BlockAddedScope, BlockTemplateScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
         SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
     },
 };
-use karlsen_rpc_core::{api::rpc::RpcApi, model::*, Notification};
+use karlsen_rpc_core::{
+    api::rpc::{RpcApi, MAX_TRANSACTIONS_BY_IDS},
+    model::*,
+    Notification,
+};
+use karlsen_rpc_service::backup::{BackupManifest, BACKUP_MANIFEST_FILE};
+use karlsen_txscript::pay_to_address_script;
 use karlsen_utils::{fd_budget, networking::ContextualNetAddress};
+use karlsen_wrpc_client::{client::ConnectOptions, KarlsenRpcClient, WrpcEncoding};
 use karlsend_lib::args::Args;
 use tokio::task::JoinHandle;
 
@@ -43,13 +52,17 @@ async fn sanity_test() {
     // As we log the panic, we want to set it up after the logger
     karlsen_core::panic::configure_panic();
 
+    let backup_tempdir = get_karlsen_tempdir();
     let args = Args {
         simnet: true,
         disable_upnp: true, // UPnP registration might take some time and is not needed for this test
         enable_unsynced_mining: true,
         block_template_cache_lifetime: Some(0),
         utxoindex: true,
+        txindex: true,
+        addresshistoryindex: true,
         unsafe_rpc: true,
+        backupdir: Some(backup_tempdir.path().to_str().unwrap().to_owned()),
         ..Default::default()
     };
 
@@ -689,6 +702,54 @@ async fn sanity_test() {
                 })
             }
 
+            KarlsendPayloadOps::GetTransaction => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let transaction_id = RpcHash::from_bytes([0; 32]);
+                    let result = rpc_client.get_transaction(transaction_id).await;
+                    // The error reaches the gRPC client as a message
+                    let expected = karlsen_rpc_core::RpcError::TransactionNotFound(transaction_id).to_string();
+                    assert!(result.is_err_and(|err| err.to_string() == expected));
+                })
+            }
+
+            KarlsendPayloadOps::GetTransactionsByIds => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let transactions = rpc_client.get_transactions_by_ids(vec![RpcHash::from_bytes([0; 32])]).await.unwrap();
+                    assert!(transactions.is_empty());
+                })
+            }
+
+            KarlsendPayloadOps::GetAddressHistory => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // No block pays to this address
+                    let address = Address::new(Prefix::Simnet, Version::PubKey, &[1u8; 32]);
+                    let response = rpc_client.get_address_history(address, None, None, None, 0).await.unwrap();
+                    assert!(response.entries.is_empty());
+                    assert!(response.next_cursor.is_none());
+                })
+            }
+
+            KarlsendPayloadOps::CreateBackup => {
+                let rpc_client = client.clone();
+                let backup_root = backup_tempdir.path().to_owned();
+                tst!(op, {
+                    let response = rpc_client.create_backup().await.unwrap();
+                    let backup_dir = std::path::PathBuf::from(&response.path);
+                    assert!(backup_dir.starts_with(&backup_root));
+                    let manifest = std::fs::read_to_string(backup_dir.join(BACKUP_MANIFEST_FILE)).unwrap();
+                    let manifest: BackupManifest = serde_json::from_str(&manifest).unwrap();
+                    assert_eq!(manifest.sink, response.sink);
+                    assert_eq!(manifest.pruning_point, response.pruning_point);
+                    assert_eq!(manifest.databases.len(), 3);
+                    for database in manifest.databases.iter() {
+                        assert!(backup_dir.join(database).join("CURRENT").exists());
+                    }
+                })
+            }
+
             KarlsendPayloadOps::NotifyBlockAdded => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -705,6 +766,15 @@ async fn sanity_test() {
                 })
             }
 
+            KarlsendPayloadOps::NotifyBlockTemplate => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    let pay_address = Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]);
+                    rpc_client.start_notify(id, BlockTemplateScope::new(pay_address, vec![]).into()).await.unwrap();
+                })
+            }
+
             KarlsendPayloadOps::NotifyFinalityConflict => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -770,6 +840,9 @@ async fn sanity_test() {
 
     let _results = try_join_all(tasks).await;
 
+    // Transactions accepted by mined blocks are served by the transaction index
+    index_test(&client, &daemon).await;
+
     // Unregister the notification listener
     assert!(client.unregister_listener(listener_id).await.is_ok());
 
@@ -784,3 +857,143 @@ async fn sanity_test() {
     drop(client);
     daemon.shutdown();
 }
+
+/// Mines two blocks on top of the sink and checks that the coinbase transaction of the first one,
+/// accepted by the second one, is served by the transaction and address history indexes over both gRPC and wRPC.
+async fn index_test(client: &GrpcClient, daemon: &Daemon) {
+    let pay_address = Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]);
+    let pay_script_public_key = pay_to_address_script(&pay_address);
+    let mut mined = Vec::with_capacity(2);
+    for _ in 0..2 {
+        let template = client.get_block_template(pay_address.clone(), vec![]).await.unwrap();
+        let header: Header = (&template.block.header).into();
+        let coinbase = Transaction::try_from(template.block.transactions[0].clone()).unwrap();
+        assert_eq!(client.submit_block(template.block, false).await.unwrap().report, SubmitBlockReport::Success);
+
+        // Wait for the block to become the sink so that the next one is mined on top of it
+        let rpc_client = client.clone();
+        wait_for(
+            50,
+            100,
+            move || {
+                let rpc_client = rpc_client.clone();
+                async move { rpc_client.get_sink().await.unwrap().sink == header.hash }
+            },
+            "the mined block should become the sink",
+        )
+        .await;
+        mined.push((header, coinbase));
+    }
+    let ((block, coinbase), (accepting_block, _)) = (&mined[0], &mined[1]);
+    let transaction_id = coinbase.id();
+
+    // The txindex is updated asynchronously from the virtual chain changes
+    let rpc_client = client.clone();
+    wait_for(
+        50,
+        100,
+        move || {
+            let rpc_client = rpc_client.clone();
+            async move { rpc_client.get_transaction(transaction_id).await.is_ok() }
+        },
+        "the accepted coinbase transaction should get indexed",
+    )
+    .await;
+
+    // So is the address history index
+    let (accepting_daa_score, accepting_pay_address) = (accepting_block.daa_score, pay_address.clone());
+    let rpc_client = client.clone();
+    wait_for(
+        50,
+        100,
+        move || {
+            let (rpc_client, address) = (rpc_client.clone(), accepting_pay_address.clone());
+            async move {
+                let response = rpc_client
+                    .get_address_history(address, Some(accepting_daa_score), Some(accepting_daa_score), None, 0)
+                    .await
+                    .unwrap();
+                response.entries.iter().any(|entry| entry.transaction_id == transaction_id)
+            }
+        },
+        "the accepted coinbase outputs should get indexed in the address history",
+    )
+    .await;
+
+    let address = daemon.args.read().rpclisten_borsh.clone().unwrap().to_address(&daemon.network.network_type, &WrpcEncoding::Borsh);
+    let wrpc_client = KarlsenRpcClient::new(
+        WrpcEncoding::Borsh,
+        Some(&format!("ws://127.0.0.1:{}", address.normalize(0).port)),
+        None,
+        Some(daemon.network),
+        None,
+    )
+    .unwrap();
+    wrpc_client.connect(Some(ConnectOptions { block_async_connect: true, ..Default::default() })).await.unwrap();
+
+    let assert_indexed_transaction = |indexed: &RpcIndexedTransaction| {
+        assert_eq!(indexed.transaction_id, transaction_id);
+        assert_eq!(indexed.block_hash, block.hash);
+        assert_eq!(indexed.index_within_block, 0);
+        assert_eq!(indexed.accepting_block_hash, accepting_block.hash);
+        assert_eq!(indexed.accepting_block_daa_score, accepting_block.daa_score);
+        let transaction = indexed.transaction.clone().expect("the containing block body should be available");
+        assert_eq!(transaction.verbose_data.as_ref().unwrap().block_hash, block.hash);
+        assert_eq!(Transaction::try_from(transaction).unwrap(), *coinbase);
+    };
+
+    let rpc_clients: [(&str, &dyn RpcApi); 2] = [("gRPC", client), ("wRPC", &wrpc_client)];
+    for (name, rpc_client) in rpc_clients {
+        info!("Checking the transaction index over {}", name);
+        assert_indexed_transaction(&rpc_client.get_transaction(transaction_id).await.unwrap());
+
+        // Unknown ids are omitted from the response
+        let transactions = rpc_client.get_transactions_by_ids(vec![RpcHash::from_bytes([0; 32]), transaction_id]).await.unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_indexed_transaction(&transactions[0]);
+
+        // Oversized requests are rejected
+        let result = rpc_client.get_transactions_by_ids(vec![transaction_id; MAX_TRANSACTIONS_BY_IDS + 1]).await;
+        assert!(result.is_err());
+
+        info!("Checking the address history index over {}", name);
+        let (start, end) = (Some(accepting_block.daa_score), Some(accepting_block.daa_score));
+        let response = rpc_client.get_address_history(pay_address.clone(), start, end, None, 0).await.unwrap();
+        assert!(response.next_cursor.is_none());
+        let received: Vec<_> = response.entries.iter().filter(|entry| entry.transaction_id == transaction_id).collect();
+        let paid_outputs: Vec<_> =
+            coinbase.outputs.iter().enumerate().filter(|(_, output)| output.script_public_key == pay_script_public_key).collect();
+        assert_eq!(received.len(), paid_outputs.len());
+        for (entry, (index, output)) in received.into_iter().zip(paid_outputs) {
+            assert_eq!(entry.kind, RpcAddressHistoryEntryKind::Received);
+            assert_eq!(entry.index, index as u32);
+            assert_eq!(entry.outpoint, RpcTransactionOutpoint { transaction_id, index: index as u32 });
+            assert_eq!(entry.amount, output.value);
+            assert_eq!(entry.accepting_block_hash, accepting_block.hash);
+            assert_eq!(entry.accepting_block_daa_score, accepting_block.daa_score);
+        }
+
+        // Walking the same range one entry at a time yields the same entries
+        let mut paginated = Vec::with_capacity(response.entries.len());
+        let mut cursor = None;
+        loop {
+            let page = rpc_client.get_address_history(pay_address.clone(), start, end, cursor, 1).await.unwrap();
+            assert!(page.entries.len() <= 1);
+            paginated.extend(page.entries.into_iter().map(|entry| (entry.transaction_id, entry.kind, entry.index)));
+            match page.next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => break,
+            }
+        }
+        assert_eq!(
+            paginated,
+            response.entries.iter().map(|entry| (entry.transaction_id, entry.kind, entry.index)).collect::<Vec<_>>()
+        );
+
+        // Malformed cursors are rejected
+        let result = rpc_client.get_address_history(pay_address.clone(), None, None, Some("00".to_string()), 0).await;
+        assert!(result.is_err());
+    }
+
+    wrpc_client.disconnect().await.unwrap();
+}